@@ -0,0 +1,54 @@
+fn main() {
+    #[cfg(feature = "builtin-dictionary")]
+    compress_builtin_dictionaries();
+}
+
+/// Compresses the word lists of each language pack of the `data` directory into `OUT_DIR` as raw deflate streams,
+/// to be embedded with `include_bytes!`, and generates the `BUILTIN_DICTIONARIES` table of
+/// `src/data_loader/builtin.rs` listing them.
+///
+/// A pack is a directory holding an `answers.txt`, and possibly a `guesses.txt`.
+#[cfg(feature = "builtin-dictionary")]
+fn compress_builtin_dictionaries() {
+    use miniz_oxide::deflate::{compress_to_vec, CompressionLevel};
+    use std::path::Path;
+
+    println!("cargo:rerun-if-changed=data");
+    let mut names: Vec<String> = std::fs::read_dir("data")
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.join("answers.txt").is_file())
+        .map(|path| path.file_name().unwrap().to_string_lossy().into_owned())
+        .collect();
+    names.sort();
+
+    let out_dir = std::env::var("OUT_DIR").unwrap();
+    let mut table = Vec::new();
+    for name in &names {
+        let has_guesses = Path::new("data").join(name).join("guesses.txt").is_file();
+        let lists: &[&str] = if has_guesses {
+            table.push(format!("builtin_dictionary!({name:?}, guesses)"));
+            &["answers", "guesses"]
+        } else {
+            table.push(format!("builtin_dictionary!({name:?})"));
+            &["answers"]
+        };
+        for list in lists {
+            let source = Path::new("data").join(name).join(format!("{list}.txt"));
+            println!("cargo:rerun-if-changed={}", source.display());
+
+            let content = std::fs::read(&source).unwrap();
+            let compressed = compress_to_vec(&content, CompressionLevel::BestCompression as u8);
+            let destination = Path::new(&out_dir).join(format!("{name}_{list}.txt.deflate"));
+            std::fs::write(destination, compressed).unwrap();
+        }
+    }
+
+    let table = format!(
+        "/// The dictionaries embedded in the binary, one per language pack of the `data` directory.\n\
+         pub const BUILTIN_DICTIONARIES: [BuiltinDictionary; {}] = [{}];\n",
+        table.len(),
+        table.join(", ")
+    );
+    std::fs::write(Path::new(&out_dir).join("builtin_dictionaries.rs"), table).unwrap();
+}
@@ -0,0 +1,194 @@
+use crate::cli::{GuessIterationError, MAX_IOERROR_TRIES};
+use core::fmt;
+use std::error::Error;
+use std::io;
+use wordlers::game_logic::FeedbackPattern;
+use wordlers::messages::{message, MessageId};
+use wordlers::solver::{filter_candidates, suggest_guesses, Constraints, FeedbackRow};
+use wordlers::user_input::{format_and_check::input_string_in_alphabet, InputSource};
+use wordlers::word_source::WordSource;
+
+const MAX_DISPLAYED_CANDIDATES: usize = 20;
+const N_SUGGESTIONS: usize = 5;
+
+/// Represents the possible errors that can occur while reading a row in assistant mode.
+#[derive(Debug)]
+pub enum AssistError {
+    /// The line is not made of a guess followed by its feedback.
+    MalformedLine,
+}
+
+impl Error for AssistError {}
+
+impl fmt::Display for AssistError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AssistError::MalformedLine => {
+                write!(f, "{}", message(MessageId::AssistMalformedLine, &[]))
+            }
+        }
+    }
+}
+
+/// Reads a line from the `input`, prompting for a guess and its feedback.
+///
+/// # Returns
+/// `None` if the input is exhausted or interrupted, the line otherwise.
+///
+/// # Errors
+/// Returns `GuessIterationError::TooManyIOErrorIteration` if the `input` failed `MAX_IOERROR_TRIES` times in a row.
+fn read_line(input: &mut impl InputSource) -> Result<Option<String>, GuessIterationError> {
+    let mut nb_incorrect_tries = 0;
+    while nb_incorrect_tries < MAX_IOERROR_TRIES {
+        println!("{}", message(MessageId::AssistPrompt, &[]));
+        match input.read_line() {
+            Ok(line) => return Ok(line),
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => {
+                println!();
+                return Ok(None);
+            }
+            Err(err) => {
+                eprintln!("{}", message(MessageId::GameReadError, &[("error", &err)]));
+                nb_incorrect_tries += 1;
+            }
+        }
+    }
+
+    Err(GuessIterationError::TooManyIOErrorIteration)
+}
+
+/// Parses a guess and its feedback typed by the user.
+///
+/// # Returns
+/// `None` if the `line` is empty, the uppercased guess and its parsed feedback otherwise.
+///
+/// # Errors
+/// Returns an error if the guess is invalid or the feedback is malformed.
+fn parse_row(
+    line: &str,
+    word_length: usize,
+    alphabet: &[char],
+) -> Result<Option<FeedbackRow>, Box<dyn Error>> {
+    let mut parts = line.split_whitespace();
+    let (Some(guess), Some(feedback)) = (parts.next(), parts.next()) else {
+        return if line.trim().is_empty() {
+            Ok(None)
+        } else {
+            Err(Box::new(AssistError::MalformedLine))
+        };
+    };
+    if parts.next().is_some() {
+        return Err(Box::new(AssistError::MalformedLine));
+    }
+
+    let guess = input_string_in_alphabet(guess, word_length, alphabet)?;
+    let feedback = feedback.parse::<FeedbackPattern>()?.to_states(&guess)?;
+    Ok(Some((guess, feedback)))
+}
+
+/// Helps the user solve a puzzle played elsewhere.
+///
+/// The user types each guess with the feedback received, e.g. `CRANE gy..g`.
+/// Guesses must be made of the characters of the `alphabet`.
+/// After each row, the remaining candidates among `words` and suggested next guesses are printed.
+/// Rows are read from the `input`, and those contradicting earlier ones are rejected.
+/// An empty line or the end of the input ends the session.
+///
+/// # Errors
+/// Returns `GuessIterationError::TooManyIOErrorIteration` if the `input` failed `MAX_IOERROR_TRIES` times in a row.
+pub fn assist_iteration<W: WordSource>(
+    words: &W,
+    word_length: usize,
+    alphabet: &[char],
+    input: &mut impl InputSource,
+) -> Result<(), GuessIterationError> {
+    let mut constraints = Constraints::new(word_length);
+    println!("{}", message(MessageId::AssistIntro, &[]));
+    println!("{}", message(MessageId::AssistStop, &[]));
+
+    while let Some(line) = read_line(input)? {
+        let (guess, feedback) = match parse_row(&line, word_length, alphabet) {
+            Ok(Some(row)) => row,
+            Ok(None) => break,
+            Err(err) => {
+                eprintln!("{err}");
+                continue;
+            }
+        };
+        if let Err(err) = constraints.add_row(&guess, &feedback) {
+            eprintln!(
+                "{}",
+                message(MessageId::AssistInconsistent, &[("error", &err)])
+            );
+            continue;
+        }
+
+        let candidates = filter_candidates(words, &constraints);
+        match candidates.len() {
+            0 => println!("{}", message(MessageId::AssistNoCandidate, &[])),
+            1 => {
+                println!(
+                    "{}",
+                    message(MessageId::AssistFound, &[("word", &candidates[0])])
+                );
+                break;
+            }
+            n_candidates => {
+                println!(
+                    "{}",
+                    message(MessageId::AssistCandidates, &[("count", &n_candidates)])
+                );
+                for candidate in candidates.iter().take(MAX_DISPLAYED_CANDIDATES) {
+                    println!("  {candidate}");
+                }
+                if n_candidates > MAX_DISPLAYED_CANDIDATES {
+                    println!("  ...");
+                }
+                println!(
+                    "{}",
+                    message(
+                        MessageId::AssistSuggestions,
+                        &[(
+                            "suggestions",
+                            &suggest_guesses(&candidates, N_SUGGESTIONS).join(", ")
+                        )]
+                    )
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wordlers::user_input::ScriptedInput;
+    use wordlers::word_source::WordList;
+
+    /// An input failing at every read.
+    struct FailingInput;
+
+    impl InputSource for FailingInput {
+        fn read_line(&mut self) -> io::Result<Option<String>> {
+            Err(io::Error::other("broken input"))
+        }
+    }
+
+    #[test]
+    fn test_assist_iteration() {
+        let words = WordList::from(["CRANE", "CRATE", "TRACE"]);
+        let alphabet: Vec<char> = ('A'..='Z').collect();
+
+        // Test case 1: The end of the input ends the session, even after invalid rows
+        let mut input =
+            ScriptedInput::from(vec![String::from("CRANE"), String::from("SLATE ..ggg")]);
+        assert!(assist_iteration(&words, 5, &alphabet, &mut input).is_ok());
+
+        // Test case 2: A failing input is given up after `MAX_IOERROR_TRIES` errors
+        assert!(matches!(
+            assist_iteration(&words, 5, &alphabet, &mut FailingInput),
+            Err(GuessIterationError::TooManyIOErrorIteration)
+        ));
+    }
+}
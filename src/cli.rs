@@ -1,10 +1,27 @@
 use colored::Colorize;
 use core::fmt;
+use std::collections::HashMap;
 use std::error::Error;
-use wordlers::game_logic::{analyze_guess, CharacterState};
-use wordlers::user_input::{format_and_check::input_string, get_user_input_stdin};
+use std::io::{self, IsTerminal, Write};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+use wordlers::data_loader::language_pack::LanguagePack;
+use wordlers::game::{rank_race, GameEvent, Observer, Outcome, RaceStanding, Round, RoundResult};
+use wordlers::game_logic::CharacterState;
+use wordlers::messages::{message, MessageId};
+use wordlers::stats::SurvivalRun;
+use wordlers::timer::{Expiry, Timer};
+use wordlers::transcript::TranscriptRound;
+use wordlers::user_input::{InputSource, StdinInput};
+use wordlers::word_source::WordSource;
 
-const MAX_IOERROR_TRIES: usize = 5;
+/// Number of input errors in a row after which the user input is given up.
+pub const MAX_IOERROR_TRIES: usize = 5;
+/// How often the time left is refreshed while waiting for a guess.
+const TIMER_TICK: Duration = Duration::from_secs(1);
+/// Input asking for a hint instead of a guess.
+pub const HINT_COMMAND: &str = "?";
 
 /// Represents the possible errors that can occur during a guess iteration.
 #[derive(Debug)]
@@ -18,9 +35,90 @@ impl Error for GuessIterationError {}
 impl fmt::Display for GuessIterationError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            GuessIterationError::TooManyIOErrorIteration => {
-                write!(f, "More than {MAX_IOERROR_TRIES} occurred.")
+            GuessIterationError::TooManyIOErrorIteration => write!(
+                f,
+                "{}",
+                message(
+                    MessageId::GameTooManyIoErrors,
+                    &[("max", &MAX_IOERROR_TRIES)]
+                )
+            ),
+        }
+    }
+}
+
+/// What the background reader of `InterruptibleStdinInput` sends to the game.
+enum StdinMessage {
+    /// A line was read, or the input ended or failed.
+    Line(io::Result<Option<String>>),
+    /// The user pressed Ctrl-C.
+    Interrupted,
+}
+
+/// Reads the lines typed by the user on stdin in a background thread, so that Ctrl-C can interrupt a pending read.
+///
+/// Ctrl-C is reported as an error of kind `std::io::ErrorKind::Interrupted`.
+pub struct InterruptibleStdinInput {
+    receiver: Receiver<StdinMessage>,
+    ended: bool,
+}
+
+impl InterruptibleStdinInput {
+    /// Starts reading stdin and handling Ctrl-C. Only one can be created per process.
+    ///
+    /// # Errors
+    /// Returns an error if the Ctrl-C handler cannot be installed.
+    pub fn new() -> Result<Self, ctrlc::Error> {
+        let (sender, receiver) = mpsc::channel();
+        let interrupt_sender = sender.clone();
+        ctrlc::set_handler(move || {
+            let _ = interrupt_sender.send(StdinMessage::Interrupted);
+        })?;
+        thread::spawn(move || loop {
+            let line = StdinInput.read_line();
+            let ended = matches!(line, Ok(None));
+            if sender.send(StdinMessage::Line(line)).is_err() || ended {
+                break;
             }
+        });
+        Ok(InterruptibleStdinInput {
+            receiver,
+            ended: false,
+        })
+    }
+}
+
+impl InterruptibleStdinInput {
+    /// Turns a `message` of the background reader into a read line, `None` meaning the reader stopped.
+    fn receive(&mut self, message: Option<StdinMessage>) -> io::Result<Option<String>> {
+        match message {
+            Some(StdinMessage::Line(line)) => {
+                self.ended = matches!(line, Ok(None));
+                line
+            }
+            Some(StdinMessage::Interrupted) => Err(io::Error::from(io::ErrorKind::Interrupted)),
+            None => Ok(None),
+        }
+    }
+}
+
+impl InputSource for InterruptibleStdinInput {
+    fn read_line(&mut self) -> io::Result<Option<String>> {
+        if self.ended {
+            return Ok(None);
+        }
+        let message = self.receiver.recv().ok();
+        self.receive(message)
+    }
+
+    fn read_line_timeout(&mut self, timeout: Duration) -> io::Result<Option<String>> {
+        if self.ended {
+            return Ok(None);
+        }
+        match self.receiver.recv_timeout(timeout) {
+            Ok(message) => self.receive(Some(message)),
+            Err(RecvTimeoutError::Timeout) => Err(io::Error::from(io::ErrorKind::TimedOut)),
+            Err(RecvTimeoutError::Disconnected) => self.receive(None),
         }
     }
 }
@@ -43,79 +141,829 @@ impl fmt::Display for DisplayedCharacterState {
     }
 }
 
-/// Gives at most `n_tries` to the user to guess the `guess_word`.
-/// Also prints previous attempts' result.
-///
-/// # Returns
-/// True if the user guessed the word, false otherwise.
+/// Returns how much a character state tells about a character, to keep the most informative one.
+fn knowledge_rank(character_state: &CharacterState) -> u8 {
+    match character_state {
+        CharacterState::NotTried => 0,
+        CharacterState::NotPresent(_) => 1,
+        CharacterState::Misplaced(_) => 2,
+        CharacterState::Good(_) => 3,
+    }
+}
+
+/// Prints the on-screen keyboard of the `language_pack`,
+/// each key being coloured by the best state its character reached in the `guess_tries`.
+fn print_keyboard(language_pack: &LanguagePack, guess_tries: &[Vec<DisplayedCharacterState>]) {
+    let mut key_states: HashMap<char, CharacterState> = HashMap::new();
+    for displayed_character_state in guess_tries.iter().flatten() {
+        let character_state = &displayed_character_state.character_state;
+        if let CharacterState::NotPresent(character)
+        | CharacterState::Misplaced(character)
+        | CharacterState::Good(character) = *character_state
+        {
+            let key_state = key_states
+                .entry(character)
+                .or_insert(CharacterState::NotTried);
+            if knowledge_rank(character_state) > knowledge_rank(key_state) {
+                *key_state = character_state.clone();
+            }
+        }
+    }
+
+    for (i, row) in language_pack.keyboard.iter().enumerate() {
+        print!("{}", " ".repeat(i));
+        for key in row {
+            match key_states.get(key) {
+                Some(character_state) => print!(
+                    "{} ",
+                    DisplayedCharacterState {
+                        character_state: character_state.clone()
+                    }
+                ),
+                None => print!("{key} "),
+            }
+        }
+        println!();
+    }
+}
+
+/// Prints the events of a round to the terminal: rejected guesses, previous attempts' result,
+/// the on-screen keyboard of the `language_pack`, hints and the final result.
+pub struct TerminalObserver<'a> {
+    language_pack: &'a LanguagePack,
+    guess_tries: Vec<Vec<DisplayedCharacterState>>,
+    n_scored: usize,
+    hide_word: bool,
+}
+
+impl<'a> TerminalObserver<'a> {
+    /// Creates an observer displaying the keyboard of the `language_pack`.
+    #[must_use]
+    pub fn new(language_pack: &'a LanguagePack) -> Self {
+        TerminalObserver {
+            language_pack,
+            guess_tries: Vec::new(),
+            n_scored: 0,
+            hide_word: false,
+        }
+    }
+
+    /// Keeps the word secret when the round finishes, for other players still guessing it.
+    #[must_use]
+    pub fn hiding_word(mut self) -> Self {
+        self.hide_word = true;
+        self
+    }
+}
+
+impl TerminalObserver<'_> {
+    /// Prints the number of guesses scored and the time they took.
+    fn print_summary(&self, elapsed: Duration) {
+        println!(
+            "{}",
+            message(
+                MessageId::GameSummary,
+                &[
+                    ("attempts", &self.n_scored),
+                    ("seconds", &format_seconds(elapsed))
+                ]
+            )
+        );
+    }
+}
+
+/// Formats a duration in seconds, to the tenth of a second.
 #[must_use]
-pub fn game_iteration(guess_word: &str, n_tries: usize) -> bool {
-    let mut guess_tries: Vec<Vec<DisplayedCharacterState>> = (0..n_tries)
-        .map(|_| {
-            vec![
-                DisplayedCharacterState {
-                    character_state: CharacterState::NotTried
-                };
-                guess_word.len()
-            ]
-        })
-        .collect();
-    for i in 0..n_tries {
-        match guess_iteration(guess_word) {
-            Ok(result) => {
-                guess_tries[i] = result;
-                println!("Current tries:");
-                for guess_try in &guess_tries {
+pub fn format_seconds(duration: Duration) -> String {
+    format!("{:.1}", duration.as_secs_f64())
+}
+
+impl Observer for TerminalObserver<'_> {
+    fn notify(&mut self, event: &GameEvent) {
+        match event {
+            GameEvent::RoundStarted(word_length, n_tries) => {
+                self.guess_tries = vec![
+                    vec![
+                        DisplayedCharacterState {
+                            character_state: CharacterState::NotTried
+                        };
+                        *word_length
+                    ];
+                    *n_tries
+                ];
+                self.n_scored = 0;
+            }
+            GameEvent::GuessRejected(_, err) => eprintln!("{err}"),
+            GameEvent::GuessScored(_, feedback, _) => {
+                self.guess_tries[self.n_scored] = feedback
+                    .iter()
+                    .map(|character_state| DisplayedCharacterState {
+                        character_state: character_state.clone(),
+                    })
+                    .collect();
+                self.n_scored += 1;
+                println!("{}", message(MessageId::GameCurrentTries, &[]));
+                for guess_try in &self.guess_tries {
                     for displayed_guess_character in guess_try {
                         print!("{displayed_guess_character}");
                     }
                     println!();
                 }
-                if guess_tries[i].iter().all(|c| {
-                    std::mem::discriminant(&c.character_state)
-                        == std::mem::discriminant(&CharacterState::Good(' '))
-                }) {
-                    return true;
+                print_keyboard(self.language_pack, &self.guess_tries);
+            }
+            GameEvent::GuessTimedOut(_) => {
+                self.guess_tries.pop();
+                println!("{}", message(MessageId::TimerTimeOut, &[]));
+            }
+            GameEvent::HintUsed(position, character) => println!(
+                "{}",
+                message(
+                    MessageId::GameHint,
+                    &[("position", &(position + 1)), ("character", character)]
+                )
+            ),
+            GameEvent::RoundFinished(Outcome::Won, _, elapsed) if self.hide_word => {
+                println!("{}", message(MessageId::RaceFound, &[]));
+                self.print_summary(*elapsed);
+            }
+            GameEvent::RoundFinished(Outcome::Lost, _, elapsed) if self.hide_word => {
+                println!("{}", message(MessageId::RaceNotFound, &[]));
+                self.print_summary(*elapsed);
+            }
+            GameEvent::RoundFinished(Outcome::Abandoned, _, _) if self.hide_word => {}
+            GameEvent::RoundFinished(Outcome::Won, word, elapsed) => {
+                println!("{}", message(MessageId::GameWin, &[("word", word)]));
+                self.print_summary(*elapsed);
+            }
+            GameEvent::RoundFinished(Outcome::Lost, word, elapsed) => {
+                println!("{}", message(MessageId::GameLose, &[("word", word)]));
+                self.print_summary(*elapsed);
+            }
+            GameEvent::RoundFinished(Outcome::Abandoned, word, _) => {
+                println!("{}", message(MessageId::GameAbandoned, &[("word", word)]));
+            }
+        }
+    }
+}
+
+/// How the rounds of a game are played.
+#[derive(Clone)]
+pub struct Rules<'a> {
+    /// The maximum number of attempts of a round.
+    pub n_tries: usize,
+    /// Whether a guess must fit the feedback and hints so far.
+    pub hard_mode: bool,
+    /// The words accepted as guesses besides the word to guess. Any word of the alphabet is accepted if it is empty.
+    pub dictionaries: Vec<&'a dyn WordSource>,
+}
+
+impl<'a> Rules<'a> {
+    /// Creates a round following the rules, to guess the `word` with the characters of the `alphabet`.
+    fn round<'r>(&self, word: &str, alphabet: &[char]) -> Round<'r>
+    where
+        'a: 'r,
+    {
+        self.dictionaries.iter().fold(
+            Round::new(word, alphabet, self.n_tries).with_hard_mode(self.hard_mode),
+            |round, words| round.with_dictionary(*words),
+        )
+    }
+}
+
+/// Gives at most `rules.n_tries` to the user to guess the `guess_word`, or `?` to get a hint.
+/// Guesses are read from the `input`; the round is abandoned if it is exhausted or interrupted.
+/// The round is displayed by a `TerminalObserver`, along with the `observers`,
+/// and its score is printed at the end unless it was abandoned.
+///
+/// # Returns
+/// The result of the round.
+///
+/// # Errors
+/// Returns `GuessIterationError::TooManyIOErrorIteration`, after abandoning the round,
+/// if the `input` failed more than `MAX_IOERROR_TRIES` times in a row.
+pub fn game_iteration(
+    guess_word: &str,
+    rules: &Rules,
+    language_pack: &LanguagePack,
+    input: &mut impl InputSource,
+    observers: Vec<&mut dyn Observer>,
+) -> Result<RoundResult, GuessIterationError> {
+    let mut terminal = TerminalObserver::new(language_pack);
+    let mut round = rules.round(guess_word, &language_pack.alphabet);
+    round.add_observer(&mut terminal);
+    for observer in observers {
+        round.add_observer(observer);
+    }
+
+    println!("{}", message(MessageId::GameHintHelp, &[]));
+    round.start();
+    let result = loop {
+        if let Some(result) = round.result() {
+            break result;
+        }
+        match read_guess(input) {
+            Ok(Some(line)) if line.trim() == HINT_COMMAND => {
+                if round.hint().is_none() {
+                    println!("{}", message(MessageId::GameNoHint, &[]));
                 }
             }
+            Ok(Some(line)) => {
+                round.guess(&line);
+            }
+            Ok(None) => round.abandon(),
             Err(err) => {
-                eprintln!("{err}");
-                break;
+                round.abandon();
+                return Err(err);
             }
         }
+    };
+    if result.outcome != Outcome::Abandoned {
+        print_score(&result);
     }
-    false
+    Ok(result)
+}
+
+/// Prints the score of a round and the hints it took.
+fn print_score(result: &RoundResult) {
+    let message_id = if result.hard_mode {
+        MessageId::GameScoreHard
+    } else {
+        MessageId::GameScore
+    };
+    println!(
+        "{}",
+        message(
+            message_id,
+            &[("score", &result.score), ("hints", &result.hints)]
+        )
+    );
 }
 
-/// Takes user input, checks for perfect and misplaced characters.
+/// Plays words given by `next_word` one after the other with `game_iteration`, until one is not found,
+/// following the `rules`. The `observer` is notified of the events of every round.
+/// The first word gets `rules.n_tries` attempts, and the attempts left unused on a word are added to the next one's.
+///
+/// # Returns
+/// The run, and how its last round ended: `Outcome::Lost`, or `Outcome::Abandoned`
+/// if the `input` was exhausted or interrupted.
+///
+/// # Errors
+/// Returns `GuessIterationError::TooManyIOErrorIteration`, after abandoning the round,
+/// if the `input` failed more than `MAX_IOERROR_TRIES` times in a row.
+pub fn survival_iteration(
+    next_word: &mut dyn FnMut() -> Option<String>,
+    rules: &Rules,
+    language_pack: &LanguagePack,
+    input: &mut impl InputSource,
+    observer: &mut dyn Observer,
+) -> Result<(SurvivalRun, Outcome), GuessIterationError> {
+    let mut run = SurvivalRun::default();
+    let mut attempts = rules.n_tries;
+    let mut outcome = Outcome::Lost;
+    while let Some(guess_word) = next_word() {
+        println!(
+            "{}",
+            message(
+                MessageId::SurvivalWord,
+                &[("number", &(run.words + 1)), ("attempts", &attempts)]
+            )
+        );
+        let result = game_iteration(
+            &guess_word,
+            &Rules {
+                n_tries: attempts,
+                ..rules.clone()
+            },
+            language_pack,
+            input,
+            vec![&mut *observer],
+        )?;
+        outcome = result.outcome;
+        if outcome != Outcome::Won {
+            break;
+        }
+        run.add_word(result.unused_attempts);
+        attempts = rules.n_tries + result.unused_attempts;
+    }
+    println!(
+        "{}",
+        message(
+            MessageId::SurvivalSummary,
+            &[("words", &run.words), ("score", &run.score)]
+        )
+    );
+    Ok((run, outcome))
+}
+
+/// Plays a race between the `players`, taking turns at the same terminal to guess the same `guess_word`
+/// in at most `rules.n_tries` each, or `?` to get a hint.
+/// The round of each player is also followed by the observer at the same index in `observers`. Each turn ends with a scored guess,
+/// and the clock of a player only runs during their turns.
+/// Once every player finished, the standings are printed: the fastest player who found the word wins,
+/// ties being broken by fewer guesses.
+///
 /// # Returns
-/// The result char array produced by `check_perfect_characters` and `check_misplaced_characters`.
+/// `Outcome::Won` if someone found the word, `Outcome::Lost` if nobody did,
+/// or `Outcome::Abandoned` if the `input` was exhausted or interrupted before the end.
+///
+/// # Errors
+/// Returns `GuessIterationError::TooManyIOErrorIteration`, after abandoning the race,
+/// if the `input` failed more than `MAX_IOERROR_TRIES` times in a row.
+pub fn race_iteration(
+    guess_word: &str,
+    rules: &Rules,
+    language_pack: &LanguagePack,
+    players: &[String],
+    input: &mut impl InputSource,
+    observers: Vec<&mut dyn Observer>,
+) -> Result<Outcome, GuessIterationError> {
+    let mut terminals: Vec<TerminalObserver> = players
+        .iter()
+        .map(|_| TerminalObserver::new(language_pack).hiding_word())
+        .collect();
+    let mut rounds: Vec<Round> = terminals
+        .iter_mut()
+        .zip(observers)
+        .map(|(terminal, observer)| {
+            let mut round = rules.round(guess_word, &language_pack.alphabet);
+            round.add_observer(terminal);
+            round.add_observer(observer);
+            round.start();
+            round
+        })
+        .collect();
+
+    println!("{}", message(MessageId::GameHintHelp, &[]));
+    while rounds.iter().any(|round| !round.is_finished()) {
+        for (player, round) in players.iter().zip(rounds.iter_mut()) {
+            if round.is_finished() {
+                continue;
+            }
+            println!("{}", message(MessageId::RaceTurn, &[("player", player)]));
+            round.resume();
+            let scored = play_turn(round, input);
+            if !matches!(scored, Ok(true)) {
+                for round in &mut rounds {
+                    round.abandon();
+                }
+                println!(
+                    "{}",
+                    message(MessageId::GameAbandoned, &[("word", &guess_word)])
+                );
+                return scored.map(|_| Outcome::Abandoned);
+            }
+        }
+    }
+
+    let mut standings: Vec<RaceStanding> = players
+        .iter()
+        .zip(&rounds)
+        .map(|(player, round)| RaceStanding {
+            player: player.clone(),
+            outcome: round.outcome().unwrap_or(Outcome::Abandoned),
+            attempts: round.rows().len(),
+            time: round.elapsed(),
+        })
+        .collect();
+    let winner = rank_race(&mut standings).map(|winner| winner.player.clone());
+    println!("{}", message(MessageId::RaceWord, &[("word", &guess_word)]));
+    print_standings(&standings);
+    match winner {
+        Some(winner) => {
+            println!("{}", message(MessageId::RaceWinner, &[("player", &winner)]));
+            Ok(Outcome::Won)
+        }
+        None => {
+            println!("{}", message(MessageId::RaceNoWinner, &[]));
+            Ok(Outcome::Lost)
+        }
+    }
+}
+
+/// Plays against the `timer`: each guess must come before the end of its countdown, or its attempt is lost.
+/// When the whole run is limited in time, as in blitz, rounds follow each other with the words given by `next_word`
+/// until the time is over, and the number of words solved is printed at the end.
+/// Otherwise a single round is played.
+///
+/// Guesses are read from the `input`, or `?` to get a hint, with the time left shown above each prompt.
+/// Rounds follow the `rules`, and are followed by the `observer`.
+///
+/// # Returns
+/// How the single round ended; for a blitz, `Outcome::Won` if a word was solved and `Outcome::Lost` otherwise.
+/// In both cases, `Outcome::Abandoned` if the `input` was exhausted or interrupted.
+///
+/// # Errors
+/// Returns `GuessIterationError::TooManyIOErrorIteration`, after abandoning the round,
+/// if the `input` failed more than `MAX_IOERROR_TRIES` times in a row.
+pub fn timed_iteration(
+    next_word: &mut dyn FnMut() -> Option<String>,
+    rules: &Rules,
+    language_pack: &LanguagePack,
+    input: &mut impl InputSource,
+    timer: &mut Timer,
+    observer: &mut dyn Observer,
+) -> Result<Outcome, GuessIterationError> {
+    let blitz = timer.is_run_limited();
+    let mut solved = 0;
+    let mut attempts = 0;
+    let mut outcome = Outcome::Lost;
+    println!("{}", message(MessageId::GameHintHelp, &[]));
+    for number in 1.. {
+        let Some(guess_word) = next_word() else {
+            break;
+        };
+        if blitz {
+            println!("{}", message(MessageId::BlitzWord, &[("number", &number)]));
+        }
+        let mut terminal = TerminalObserver::new(language_pack);
+        let mut round = rules
+            .round(&guess_word, &language_pack.alphabet)
+            .with_clock(timer.clock());
+        round.add_observer(&mut terminal);
+        round.add_observer(&mut *observer);
+        round.start();
+        let run_over = play_timed_round(&mut round, input, timer)?;
+        attempts += round.rows().len();
+        outcome = round.outcome().unwrap_or(Outcome::Abandoned);
+        if outcome == Outcome::Won {
+            solved += 1;
+        }
+        if !blitz {
+            return Ok(outcome);
+        }
+        if run_over {
+            outcome = if solved > 0 {
+                Outcome::Won
+            } else {
+                Outcome::Lost
+            };
+            break;
+        }
+        if outcome == Outcome::Abandoned {
+            break;
+        }
+    }
+    println!(
+        "{}",
+        message(
+            MessageId::BlitzSummary,
+            &[("solved", &solved), ("attempts", &attempts)]
+        )
+    );
+    Ok(outcome)
+}
+
+/// Reads guesses from the `input` until the `round` finishes, each guess against the countdown of the `timer`.
+/// A guess coming too late loses its attempt. The round is abandoned if the time of the run is over,
+/// or if the input is exhausted or interrupted.
+///
+/// # Returns
+/// `true` if the time of the run is over.
+///
+/// # Errors
+/// Returns `GuessIterationError::TooManyIOErrorIteration`, after abandoning the round,
+/// if the `input` failed too many times.
+fn play_timed_round(
+    round: &mut Round,
+    input: &mut impl InputSource,
+    timer: &mut Timer,
+) -> Result<bool, GuessIterationError> {
+    timer.start_guess();
+    while !round.is_finished() {
+        match read_timed_guess(input, timer) {
+            Ok(TimedRead::Line(line)) if line.trim() == HINT_COMMAND => {
+                if round.hint().is_none() {
+                    println!("{}", message(MessageId::GameNoHint, &[]));
+                }
+            }
+            Ok(TimedRead::Line(line)) => {
+                if round.guess(&line).is_some() {
+                    timer.start_guess();
+                }
+            }
+            Ok(TimedRead::Expired(Expiry::Guess)) => {
+                round.time_out();
+                timer.start_guess();
+            }
+            Ok(TimedRead::Expired(Expiry::Run)) => {
+                println!("{}", message(MessageId::BlitzTimeUp, &[]));
+                round.abandon();
+                return Ok(true);
+            }
+            Ok(TimedRead::Ended) => round.abandon(),
+            Err(err) => {
+                round.abandon();
+                return Err(err);
+            }
+        }
+    }
+    Ok(false)
+}
+
+/// What came of waiting for a guess against the clock.
+enum TimedRead {
+    /// A line was typed in time.
+    Line(String),
+    /// The input is exhausted, or the user pressed Ctrl-C.
+    Ended,
+    /// A time limit is over.
+    Expired(Expiry),
+}
+
+/// Formats the time left as minutes and seconds, rounded up so that `0:00` only shows once the time is over.
+fn format_remaining(remaining: Duration) -> String {
+    let seconds = remaining.as_millis().div_ceil(1000);
+    format!("{}:{:02}", seconds / 60, seconds % 60)
+}
+
+/// Prompts the user for a guess, read from the `input` before the next deadline of the `timer`.
+/// The time left is printed above the prompt and, on a terminal, refreshed in place every second.
+///
+/// # Errors
+/// Throw `GuessIterationError::TooManyIOErrorIteration` if the input failed more than `MAX_IOERROR_TRIES` times.
+fn read_timed_guess(
+    input: &mut impl InputSource,
+    timer: &Timer,
+) -> Result<TimedRead, GuessIterationError> {
+    let live = io::stdout().is_terminal();
+    let time_left = || {
+        let remaining = timer.remaining().unwrap_or_default();
+        message(
+            MessageId::TimerRemaining,
+            &[("time", &format_remaining(remaining))],
+        )
+    };
+    let mut nb_incorrect_tries = 0;
+    while nb_incorrect_tries < MAX_IOERROR_TRIES {
+        println!("{}", time_left());
+        println!("{}", message(MessageId::GamePrompt, &[]));
+        loop {
+            if let Some(expiry) = timer.expired() {
+                return Ok(TimedRead::Expired(expiry));
+            }
+            let wait = timer
+                .remaining()
+                .map_or(TIMER_TICK, |remaining| remaining.min(TIMER_TICK));
+            match input.read_line_timeout(wait) {
+                Ok(Some(line)) => {
+                    return Ok(timer
+                        .expired()
+                        .map_or(TimedRead::Line(line), TimedRead::Expired));
+                }
+                Ok(None) => return Ok(TimedRead::Ended),
+                Err(err) if err.kind() == io::ErrorKind::TimedOut => {
+                    if live {
+                        // Rewrite the line two lines above the cursor, keeping what the user is typing.
+                        print!("\x1b7\x1b[2A\r\x1b[2K{}\x1b8", time_left());
+                        let _ = io::stdout().flush();
+                    }
+                }
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => {
+                    println!();
+                    return Ok(TimedRead::Ended);
+                }
+                Err(err) => {
+                    eprintln!("{}", message(MessageId::GameReadError, &[("error", &err)]));
+                    nb_incorrect_tries += 1;
+                    break;
+                }
+            }
+        }
+    }
+
+    Err(GuessIterationError::TooManyIOErrorIteration)
+}
+
+/// Reads from the `input` until a guess is scored in the `round`, giving hints on `?`.
+///
+/// # Returns
+/// `true` once a guess is scored, `false` if the input is exhausted or interrupted.
+///
+/// # Errors
+/// Throw `GuessIterationError::TooManyIOErrorIteration` if the input failed too many times.
+fn play_turn(round: &mut Round, input: &mut impl InputSource) -> Result<bool, GuessIterationError> {
+    loop {
+        match read_guess(input)? {
+            Some(line) if line.trim() == HINT_COMMAND => {
+                if round.hint().is_none() {
+                    println!("{}", message(MessageId::GameNoHint, &[]));
+                }
+            }
+            Some(line) => {
+                if round.guess(&line).is_some() {
+                    return Ok(true);
+                }
+            }
+            None => return Ok(false),
+        }
+    }
+}
+
+/// Prints the ranked `standings` of a race.
+fn print_standings(standings: &[RaceStanding]) {
+    println!("{}", message(MessageId::RaceResults, &[]));
+    for (rank, standing) in (1..).zip(standings) {
+        let message_id = if standing.outcome == Outcome::Won {
+            MessageId::RaceStandingWon
+        } else {
+            MessageId::RaceStandingLost
+        };
+        println!(
+            "{}",
+            message(
+                message_id,
+                &[
+                    ("rank", &rank),
+                    ("player", &standing.player),
+                    ("attempts", &standing.attempts),
+                    ("seconds", &format_seconds(standing.time)),
+                ]
+            )
+        );
+    }
+}
+
+/// Prompts the user for a guess, read from the `input`.
+/// # Returns
+/// The line entered by the user, or `None` if the input is exhausted or the user pressed Ctrl-C.
 ///
 /// # Errors
 /// Throw `GuessIterationError::TooManyIOErrorIteration` if there where more then `MAX_IOERROR_TRIES` tries
 /// with error while reading user's input.
-fn guess_iteration(guess_word: &str) -> Result<Vec<DisplayedCharacterState>, Box<dyn Error>> {
+fn read_guess(input: &mut impl InputSource) -> Result<Option<String>, GuessIterationError> {
     let mut nb_incorrect_tries = 0;
     while nb_incorrect_tries < MAX_IOERROR_TRIES {
-        println!("Please input a new guess:");
-        match get_user_input_stdin() {
-            Ok(user_input_str) => match input_string(&user_input_str, guess_word.len()) {
-                Ok(trimmed_uppercased_input) => {
-                    return Ok(analyze_guess(guess_word, &trimmed_uppercased_input)
-                        .into_iter()
-                        .map(|character_state| DisplayedCharacterState {
-                            character_state: character_state.clone(),
-                        })
-                        .collect());
-                }
-                Err(err) => eprintln!("{err}"),
-            },
+        println!("{}", message(MessageId::GamePrompt, &[]));
+        match input.read_line() {
+            Ok(line) => return Ok(line),
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => {
+                println!();
+                return Ok(None);
+            }
             Err(err) => {
-                eprintln!("Error while reading user input: {err}");
+                eprintln!("{}", message(MessageId::GameReadError, &[("error", &err)]));
                 nb_incorrect_tries += 1;
             }
         }
     }
 
-    Err(Box::new(GuessIterationError::TooManyIOErrorIteration))
+    Err(GuessIterationError::TooManyIOErrorIteration)
+}
+
+/// Waits after each step of a replayed round, so that the game can be followed as it unfolds.
+struct Pacer(Duration);
+
+impl Observer for Pacer {
+    fn notify(&mut self, event: &GameEvent) {
+        if matches!(
+            event,
+            GameEvent::GuessScored(..) | GameEvent::HintUsed(..) | GameEvent::GuessTimedOut(_)
+        ) {
+            thread::sleep(self.0);
+        }
+    }
+}
+
+/// Replays the `round` of a transcript, numbered `number` from 1, as it was played,
+/// with the board and keyboard of the `language_pack`, waiting `delay` after each step.
+/// The steps that do not match the rules are printed after the round.
+///
+/// # Returns
+/// The number of steps not matching the rules.
+pub fn replay_round(
+    number: usize,
+    round: &TranscriptRound,
+    language_pack: &LanguagePack,
+    delay: Duration,
+) -> usize {
+    let seed = round
+        .settings
+        .seed
+        .map_or_else(|| String::from("-"), |seed| seed.to_string());
+    println!(
+        "{}",
+        message(
+            MessageId::ReplayRound,
+            &[
+                ("number", &number),
+                ("player", &round.settings.player),
+                ("mode", &round.settings.mode),
+                ("seed", &seed),
+            ]
+        )
+    );
+    let mut terminal = TerminalObserver::new(language_pack);
+    let mut pacer = Pacer(delay);
+    let mismatches = round.replay(&language_pack.alphabet, vec![&mut terminal, &mut pacer]);
+    for mismatch in &mismatches {
+        eprintln!("{mismatch}");
+    }
+    mismatches.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+    use wordlers::data_loader::WordHashMap;
+    use wordlers::stats::{GameMode, StatsRecorder};
+    use wordlers::timer::ManualClock;
+
+    /// Types lines on a `ManualClock`, each line taking its own delay.
+    struct SlowInput<'c> {
+        clock: &'c ManualClock,
+        lines: VecDeque<(u64, &'static str)>,
+    }
+
+    impl<'c> SlowInput<'c> {
+        /// Creates an input typing the `lines`, each after its delay in seconds.
+        fn new(clock: &'c ManualClock, lines: &[(u64, &'static str)]) -> Self {
+            SlowInput {
+                clock,
+                lines: lines.iter().copied().collect(),
+            }
+        }
+    }
+
+    impl InputSource for SlowInput<'_> {
+        fn read_line(&mut self) -> io::Result<Option<String>> {
+            Ok(self.lines.pop_front().map(|(delay, line)| {
+                self.clock.advance(Duration::from_secs(delay));
+                String::from(line)
+            }))
+        }
+    }
+
+    fn language_pack() -> LanguagePack {
+        LanguagePack::from_parts(
+            "en",
+            "name = English\nalphabet = ABCDEFGHIJKLMNOPQRSTUVWXYZ",
+            "",
+            WordHashMap::new(),
+            WordHashMap::new(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_timed_iteration() {
+        let language_pack = language_pack();
+        let clock = ManualClock::new();
+        let seconds = |seconds| Some(Duration::from_secs(seconds));
+        let play_recorded = |words: &[&str], per_guess, blitz, lines: &[(u64, &'static str)]| {
+            let mut words: VecDeque<String> =
+                words.iter().map(|word| String::from(*word)).collect();
+            let mut timer = Timer::new(&clock, per_guess, blitz);
+            let mut recorder = StatsRecorder::new(GameMode::Blitz, false);
+            let outcome = timed_iteration(
+                &mut || words.pop_front(),
+                &Rules {
+                    n_tries: 3,
+                    hard_mode: false,
+                    dictionaries: Vec::new(),
+                },
+                &language_pack,
+                &mut SlowInput::new(&clock, lines),
+                &mut timer,
+                &mut recorder,
+            )
+            .unwrap();
+            let outcomes: Vec<Outcome> = recorder
+                .records()
+                .iter()
+                .map(|record| record.outcome)
+                .collect();
+            (outcome, outcomes)
+        };
+        let play = |words: &[&str], per_guess, blitz, lines: &[(u64, &'static str)]| {
+            play_recorded(words, per_guess, blitz, lines).0
+        };
+
+        // Test case 1: A late guess loses its attempt, but not the round
+        let lines = [(5, "crate"), (20, "crane"), (5, "crane")];
+        assert_eq!(play(&["CRANE"], seconds(10), None, &lines), Outcome::Won);
+
+        // Test case 2: The round is lost once every attempt timed out
+        let lines = [(11, "crane"), (11, "crane"), (11, "crane")];
+        assert_eq!(play(&["CRANE"], seconds(10), None, &lines), Outcome::Lost);
+
+        // Test case 3: A blitz chains words until the time is over, the round cut short being recorded as abandoned
+        let lines = [(10, "crane"), (10, "house"), (50, "slate")];
+        let words = ["CRANE", "HOUSE", "SLATE"];
+        assert_eq!(
+            play_recorded(&words, None, seconds(60), &lines),
+            (
+                Outcome::Won,
+                vec![Outcome::Won, Outcome::Won, Outcome::Abandoned]
+            )
+        );
+        assert_eq!(
+            play(&words, None, seconds(60), &[(70, "crane")]),
+            Outcome::Lost
+        );
+
+        // Test case 4: The end of the input abandons the blitz
+        let lines = [(10, "crane")];
+        assert_eq!(play(&words, None, seconds(60), &lines), Outcome::Abandoned);
+    }
 }
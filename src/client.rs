@@ -0,0 +1,181 @@
+use crate::cli::{format_seconds, InterruptibleStdinInput, TerminalObserver, HINT_COMMAND};
+use crate::error::AppError;
+use std::io::BufReader;
+use std::net::TcpStream;
+use std::thread;
+use std::time::Duration;
+use wordlers::data_loader::language_pack::LanguagePack;
+use wordlers::game::{GameEvent, Observer, Outcome};
+use wordlers::messages::{message, MessageId};
+use wordlers::protocol::{write_json_line, GameStatus, Request, Response};
+use wordlers::user_input::{InputSource, ReaderInput, StdinInput};
+
+/// Reads the guesses of the user and sends them to the server, asking for a hint on `?`.
+/// The game is abandoned when the input is exhausted or interrupted.
+fn send_guesses(requests: &mut TcpStream) {
+    let mut input: Box<dyn InputSource> = match InterruptibleStdinInput::new() {
+        Ok(input) => Box::new(input),
+        Err(_) => Box::new(StdinInput),
+    };
+    loop {
+        let request = match input.read_line() {
+            Ok(Some(line)) if line.trim() == HINT_COMMAND => Request::Hint,
+            Ok(Some(line)) => Request::Guess { word: line },
+            Ok(None) | Err(_) => Request::Quit,
+        };
+        let quit = request == Request::Quit;
+        if write_json_line(requests, &request).is_err() || quit {
+            break;
+        }
+    }
+}
+
+/// Prints how the game of another `player` of the room ended.
+fn print_player_finished(player: &str, status: GameStatus, attempts: usize, time_ms: u64) {
+    let message_id = match status {
+        GameStatus::Won => MessageId::ServerPlayerWon,
+        GameStatus::Lost => MessageId::ServerPlayerLost,
+        GameStatus::Abandoned | GameStatus::InProgress => MessageId::ServerPlayerAbandoned,
+    };
+    println!(
+        "{}",
+        message(
+            message_id,
+            &[
+                ("player", &player),
+                ("attempts", &attempts),
+                ("seconds", &format_seconds(Duration::from_millis(time_ms)))
+            ]
+        )
+    );
+}
+
+/// Joins the `room` of the server at `address` under the name of `player`, and plays its word in the terminal.
+///
+/// The guesses typed by the user are scored by the server, and displayed with the keyboard of the `language_pack`.
+/// The other players are shown as they finish, until every player of the room finished.
+///
+/// # Returns
+/// How the game of the user ended.
+///
+/// # Errors
+/// Returns an error if the server cannot be reached, or refuses to let the user join.
+pub fn join_game(
+    address: &str,
+    room: &str,
+    player: &str,
+    language_pack: &LanguagePack,
+) -> Result<Outcome, AppError> {
+    let stream = TcpStream::connect(address)
+        .map_err(|err| AppError::Connection(String::from(address), err))?;
+    let mut requests = stream.try_clone()?;
+    write_json_line(
+        &mut requests,
+        &Request::Join {
+            room: String::from(room),
+            player: String::from(player),
+        },
+    )?;
+    thread::spawn(move || send_guesses(&mut requests));
+
+    let mut terminal = TerminalObserver::new(language_pack);
+    let mut joined = false;
+    let mut outcome = None;
+    let mut elapsed = Duration::ZERO;
+    let mut responses = ReaderInput::new(BufReader::new(stream));
+    while let Some(line) = responses.read_line()? {
+        let Ok(response) = serde_json::from_str::<Response>(&line) else {
+            continue;
+        };
+        match response {
+            Response::Joined {
+                room,
+                length,
+                max_attempts,
+            } => {
+                joined = true;
+                println!("{}", message(MessageId::ServerJoined, &[("room", &room)]));
+                terminal.notify(&GameEvent::RoundStarted(length, max_attempts));
+                println!("{}", message(MessageId::GameHintHelp, &[]));
+                println!("{}", message(MessageId::GamePrompt, &[]));
+            }
+            Response::Feedback {
+                guess,
+                feedback,
+                status,
+                elapsed_ms,
+                word,
+                ..
+            } => {
+                let total = Duration::from_millis(elapsed_ms);
+                terminal.notify(&GameEvent::GuessScored(
+                    guess,
+                    feedback,
+                    total.saturating_sub(elapsed),
+                ));
+                elapsed = total;
+                if let (Some(finished), Some(word)) = (status.outcome(), word) {
+                    outcome = Some(finished);
+                    terminal.notify(&GameEvent::RoundFinished(finished, word, elapsed));
+                    println!("{}", message(MessageId::ServerWaiting, &[]));
+                } else {
+                    println!("{}", message(MessageId::GamePrompt, &[]));
+                }
+            }
+            Response::Hint {
+                position,
+                character,
+                ..
+            } => {
+                terminal.notify(&GameEvent::HintUsed(position, character));
+                println!("{}", message(MessageId::GamePrompt, &[]));
+            }
+            Response::Error { message, .. } if !joined => {
+                return Err(AppError::Other(message.into()));
+            }
+            Response::Error { message, .. } => eprintln!("{message}"),
+            Response::PlayerJoined { player: other } => {
+                println!(
+                    "{}",
+                    message(MessageId::ServerPlayerJoined, &[("player", &other)])
+                );
+            }
+            Response::PlayerFinished {
+                player: other,
+                status,
+                attempts,
+                time_ms,
+            } => {
+                if other != player {
+                    print_player_finished(&other, status, attempts, time_ms);
+                }
+            }
+            Response::RoundOver { word, winner } => {
+                if outcome.is_none() {
+                    terminal.notify(&GameEvent::RoundFinished(
+                        Outcome::Abandoned,
+                        word.clone(),
+                        elapsed,
+                    ));
+                }
+                println!(
+                    "{}",
+                    message(MessageId::ServerRoundOver, &[("word", &word)])
+                );
+                if let Some(winner) = winner {
+                    println!("{}", message(MessageId::RaceWinner, &[("player", &winner)]));
+                }
+                return Ok(outcome.unwrap_or(Outcome::Abandoned));
+            }
+            Response::Goodbye { word } => {
+                if let Some(word) = word {
+                    terminal.notify(&GameEvent::RoundFinished(Outcome::Abandoned, word, elapsed));
+                }
+                return Ok(outcome.unwrap_or(Outcome::Abandoned));
+            }
+            Response::GameStarted { .. } => {}
+        }
+    }
+    eprintln!("{}", message(MessageId::ServerDisconnected, &[]));
+    Ok(outcome.unwrap_or(Outcome::Abandoned))
+}
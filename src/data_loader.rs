@@ -1,105 +1,468 @@
+use crate::messages::{message, MessageId};
+use crate::word_source::WordSource;
 use core::fmt;
-use rand::seq::SliceRandom;
-use std::collections::{HashMap, HashSet};
+use std::collections::HashMap;
 use std::fmt::{Display, Formatter};
+use std::fs::File;
 use std::hash::BuildHasher;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+pub mod build;
+#[cfg(feature = "builtin-dictionary")]
+pub mod builtin;
+pub mod compiled;
+pub mod language_pack;
+
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+
+/// Frequency given to words listed without one.
+pub const DEFAULT_FREQUENCY: f64 = 1.0;
+
+/// Words organized by their length, each word being mapped to its frequency.
+pub type WordHashMap = HashMap<usize, HashMap<String, f64>>;
+
+/// Words loaded from a file by `load_word_source`.
+#[derive(Debug)]
+pub enum LoadedWords {
+    /// The words of a text list, organized by length.
+    List(WordHashMap),
+    /// A compiled dictionary, used in place without building a `String` per word.
+    Compiled(compiled::CompiledDictionary<Vec<u8>>),
+}
 
 /// Custom error type for data loading errors.
 #[derive(Debug)]
 pub enum Error {
-    /// Error indicating that a file contains non-alphabetic characters.
-    NotAlphabetic(String),
+    /// Error indicating that a file contains non-alphabetic characters. (path, line number, non-alphabetic characters)
+    NotAlphabetic(String, usize, Vec<char>),
     /// Error indicating that no word of a specific length was found.
     NoWordThisLength(usize),
     /// Error indicating that a vector of words is empty for a specific length.
     EmptyWordVec(usize),
+    /// Error indicating that a file contains a frequency that is not a non-negative number, or a rank of 0. (path, line number, value)
+    InvalidFrequency(String, usize, String),
+    /// Error indicating that a file is compressed with a format this build does not support. (path, format)
+    UnsupportedCompression(String, &'static str),
+    /// Error indicating that a compiled dictionary is truncated or does not match its checksum. (path)
+    CorruptedDictionary(String),
+    /// Error indicating that a compiled dictionary uses a format version this build does not support. (path, version)
+    UnsupportedDictionaryVersion(String, u32),
+    /// Error indicating that words cannot be compiled because they use too many distinct characters. (number of characters)
+    AlphabetTooLarge(usize),
 }
 
 impl std::error::Error for Error {}
 
 impl Display for Error {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        match self {
-            Error::NotAlphabetic(path) => {
-                write!(f, "File {path} contains non-alphabetic characters.")
+        let text = match self {
+            Error::NotAlphabetic(path, line_number, not_alphabetic_characters) => message(
+                MessageId::DataLoaderNotAlphabetic,
+                &[
+                    ("path", path),
+                    ("line", line_number),
+                    ("characters", &format!("{not_alphabetic_characters:?}")),
+                ],
+            ),
+            Error::NoWordThisLength(word_size) => message(
+                MessageId::DataLoaderNoWordThisLength,
+                &[("length", word_size)],
+            ),
+            Error::EmptyWordVec(word_size) => {
+                message(MessageId::DataLoaderEmptyWordVec, &[("length", word_size)])
             }
-            Error::NoWordThisLength(word_size) => {
-                write!(f, "No word of length {word_size} found.")
+            Error::InvalidFrequency(path, line_number, value) => message(
+                MessageId::DataLoaderInvalidFrequency,
+                &[
+                    ("path", path),
+                    ("line", line_number),
+                    ("value", &format!("{value:?}")),
+                ],
+            ),
+            Error::UnsupportedCompression(path, format) => message(
+                MessageId::DataLoaderUnsupportedCompression,
+                &[("path", path), ("format", format)],
+            ),
+            Error::CorruptedDictionary(path) => {
+                message(MessageId::DataLoaderCorruptedDictionary, &[("path", path)])
             }
-            Error::EmptyWordVec(word_size) => {
-                write!(f, "Vector is empty for length {word_size}.")
+            Error::UnsupportedDictionaryVersion(path, version) => message(
+                MessageId::DataLoaderUnsupportedDictionaryVersion,
+                &[("path", path), ("version", version)],
+            ),
+            Error::AlphabetTooLarge(count) => {
+                message(MessageId::DataLoaderAlphabetTooLarge, &[("count", count)])
+            }
+        };
+        write!(f, "{text}")
+    }
+}
+
+/// Opens a words file for reading, transparently decompressing it if needed.
+///
+/// The compression format is detected from the magic bytes of the file or from its extension:
+/// - gzip (`.gz`), requires the `gzip` feature.
+/// - zstd (`.zst`), requires the `zstd` feature.
+///
+/// # Errors
+///
+/// - `wordlers::data_loader::Error::UnsupportedCompression` if the file is compressed with a disabled format.
+/// - `std::io::Error` if the file cannot be read.
+fn open_words_file(data_file_path: &str) -> Result<Box<dyn BufRead>, Box<dyn std::error::Error>> {
+    let mut reader = BufReader::new(File::open(data_file_path)?);
+    let magic = reader.fill_buf()?;
+    let extension = Path::new(data_file_path)
+        .extension()
+        .and_then(|extension| extension.to_str());
+
+    if magic.starts_with(&GZIP_MAGIC) || extension == Some("gz") {
+        #[cfg(feature = "gzip")]
+        return Ok(Box::new(BufReader::new(
+            flate2::bufread::MultiGzDecoder::new(reader),
+        )));
+        #[cfg(not(feature = "gzip"))]
+        return Err(Box::new(Error::UnsupportedCompression(
+            String::from(data_file_path),
+            "gzip",
+        )));
+    }
+    if magic.starts_with(&ZSTD_MAGIC) || extension == Some("zst") {
+        #[cfg(feature = "zstd")]
+        return Ok(Box::new(BufReader::new(
+            zstd::stream::read::Decoder::with_buffer(reader)?,
+        )));
+        #[cfg(not(feature = "zstd"))]
+        return Err(Box::new(Error::UnsupportedCompression(
+            String::from(data_file_path),
+            "zstd",
+        )));
+    }
+
+    Ok(Box::new(reader))
+}
+
+/// Parses a line of a words file.
+///
+/// Blank lines and comments (starting with `#`, on their own line or after a word) are ignored,
+/// as well as surrounding whitespace, including the carriage return of Windows line endings.
+///
+/// # Returns
+///
+/// `None` if the line holds no word, otherwise the word and its frequency,
+/// `DEFAULT_FREQUENCY` if the line has no second column.
+/// Ranks are converted to a frequency of `1 / rank`.
+///
+/// # Errors
+///
+/// - `wordlers::data_loader::Error::NotAlphabetic` if the word contains non-alphabetic characters.
+/// - `wordlers::data_loader::Error::InvalidFrequency` if the frequency is not a non-negative number, or the rank is 0.
+fn parse_line<'a>(
+    data_file_path: &str,
+    line_number: usize,
+    line: &'a str,
+    is_rank: bool,
+) -> Result<Option<(&'a str, f64)>, Error> {
+    let line = line
+        .split_once('#')
+        .map_or(line, |(content, _)| content)
+        .trim();
+    if line.is_empty() {
+        return Ok(None);
+    }
+
+    let (word, frequency) = match line.split_once(['\t', ',']) {
+        None => (line, DEFAULT_FREQUENCY),
+        Some((word, value)) => {
+            let invalid_frequency = || {
+                Error::InvalidFrequency(
+                    String::from(data_file_path),
+                    line_number,
+                    String::from(value.trim()),
+                )
+            };
+            let value: f64 = value.trim().parse().map_err(|_| invalid_frequency())?;
+            if !value.is_finite() || value < 0.0 || (is_rank && value == 0.0) {
+                return Err(invalid_frequency());
+            }
+            (word.trim_end(), if is_rank { value.recip() } else { value })
+        }
+    };
+
+    let not_alphabetic_characters = word
+        .chars()
+        .filter(|c| !c.is_alphabetic())
+        .collect::<Vec<char>>();
+    if !not_alphabetic_characters.is_empty() {
+        return Err(Error::NotAlphabetic(
+            String::from(data_file_path),
+            line_number,
+            not_alphabetic_characters,
+        ));
+    }
+
+    Ok(Some((word, frequency)))
+}
+
+/// Diagnostics gathered while loading a words file.
+#[derive(Debug, Default)]
+pub struct LoadReport {
+    /// The errors of the invalid lines that were skipped.
+    pub invalid_lines: Vec<Error>,
+    /// The line number and uppercased word of each entry already listed on a previous line.
+    pub duplicate_lines: Vec<(usize, String)>,
+}
+
+/// Loads words from a reader, either failing at the first invalid line or skipping them.
+///
+/// `data_file_path` only names the source in errors.
+///
+/// # Returns
+///
+/// The words organized by length, and the diagnostics of the skipped and duplicate lines.
+fn load_words(
+    reader: impl BufRead,
+    data_file_path: &str,
+    lenient: bool,
+) -> Result<(WordHashMap, LoadReport), Box<dyn std::error::Error>> {
+    let mut word_hashmap = WordHashMap::new();
+    let mut report = LoadReport::default();
+    let mut is_rank = false;
+    let mut is_first_line = true;
+    for (i, line) in reader.lines().enumerate() {
+        let line = line?;
+        if is_first_line {
+            let header = line.trim().to_lowercase();
+            if header == "word,frequency" || header == "word\tfrequency" {
+                is_first_line = false;
+                continue;
+            }
+            if header == "word,rank" || header == "word\trank" {
+                is_first_line = false;
+                is_rank = true;
+                continue;
             }
         }
+
+        match parse_line(data_file_path, i + 1, &line, is_rank) {
+            Ok(None) => {}
+            Ok(Some((word, frequency))) => {
+                is_first_line = false;
+                let word = word.to_uppercase();
+                let words = word_hashmap.entry(word.chars().count()).or_default();
+                if let Some(known_frequency) = words.get_mut(&word) {
+                    *known_frequency = known_frequency.max(frequency);
+                    report.duplicate_lines.push((i + 1, word));
+                } else {
+                    words.insert(word, frequency);
+                }
+            }
+            Err(err) if lenient => {
+                is_first_line = false;
+                report.invalid_lines.push(err);
+            }
+            Err(err) => return Err(Box::new(err)),
+        }
+    }
+
+    Ok((word_hashmap, report))
+}
+
+/// Loads words from a file, either a compiled dictionary or a text list, see `load_words`.
+/// The words of a compiled dictionary are copied into the hash map, see `load_word_source` to avoid it.
+fn load_words_path(
+    data_file_path: &str,
+    lenient: bool,
+) -> Result<(WordHashMap, LoadReport), Box<dyn std::error::Error>> {
+    if let Some(dictionary) = compiled::open_compiled(data_file_path)? {
+        return Ok((dictionary.to_word_hashmap(), LoadReport::default()));
     }
+    load_words(open_words_file(data_file_path)?, data_file_path, lenient)
 }
 
 /// Loads words from a file and organizes them into a hashmap based on their length.
 ///
+/// The file is read line by line and may be gzip- or zstd-compressed (see the `gzip` and `zstd` features).
+/// It may also be a compiled dictionary (see `wordlers::data_loader::compiled`), detected from its magic bytes.
+/// Its words are then copied into the hash map: use `load_word_source` to look them up in place instead.
+/// Each line holds a word, optionally followed by a tab or a comma and its frequency (higher is more common).
+/// Blank lines, `#` comments and surrounding whitespace are ignored.
+/// A first line `word,frequency` or `word,rank` is accepted as a header, in which case the second column
+/// may hold a rank (1 is the most common) instead, converted to a frequency of `1 / rank`.
+/// Words without a frequency are given `DEFAULT_FREQUENCY`.
+///
 /// # Arguments
 ///
 /// * `data_file_path` - The path to the file containing the words.
 ///
 /// # Returns
 ///
-/// Returns a `Result` containing a hashmap where the keys are word lengths and the values are sets of words.
+/// Returns a `Result` containing a hashmap where the keys are word lengths and the values map words to their frequency.
 ///
 /// # Errors
 ///
-/// - `wordlers::data_loader::Error` if the file contains non-alphabetic characters.
-/// - `std::io::Error` if the file cannot be read.
+/// - `wordlers::data_loader::Error` at the first line containing non-alphabetic characters or an invalid frequency,
+///   if the file uses an unsupported compression, or is a corrupted or unsupported compiled dictionary.
+/// - `std::io::Error` if the file cannot be read or decompressed.
 ///
 /// # Examples
 ///
 /// ```no_run
 /// use wordlers::data_loader::load_words_file;
-/// use std::collections::HashSet;
 /// use std::io::Write;
 ///
 /// // Initialize a temporary file with words
 /// let mut file = tempfile::NamedTempFile::new().unwrap();
 /// // 2 words of length 5, 1 word of length 9, 1 words of length 6
-/// write!(file, "apple\npeach\npineapple\ncherry").unwrap();
+/// write!(file, "apple\t120\npeach\t35.5\npineapple\ncherry").unwrap();
 ///
 /// let result = load_words_file(file.path().to_str().unwrap());
 /// assert!(result.is_ok());
 /// let word_hashmap = result.unwrap();
-/// assert_eq!(word_hashmap.get(&5).unwrap(), &HashSet::from_iter(vec!["APPLE".to_string(), "PEACH".to_string()]));
-/// assert_eq!(word_hashmap.get(&6).unwrap(), &HashSet::from_iter(vec!["CHERRY".to_string()]));
-/// assert_eq!(word_hashmap.get(&9).unwrap(), &HashSet::from_iter(vec!["PINEAPPLE".to_string()]));
+/// assert_eq!(word_hashmap.get(&5).unwrap().get("APPLE"), Some(&120.0));
+/// assert_eq!(word_hashmap.get(&5).unwrap().get("PEACH"), Some(&35.5));
+/// assert_eq!(word_hashmap.get(&6).unwrap().get("CHERRY"), Some(&1.0));
+/// assert_eq!(word_hashmap.get(&9).unwrap().len(), 1);
 /// ```
-pub fn load_words_file(
+pub fn load_words_file(data_file_path: &str) -> Result<WordHashMap, Box<dyn std::error::Error>> {
+    load_words_path(data_file_path, false).map(|(word_hashmap, _)| word_hashmap)
+}
+
+/// Loads words from a file like `load_words_file`, but skips invalid lines instead of failing.
+///
+/// # Returns
+///
+/// Returns a `Result` containing the words organized by length,
+/// and a `wordlers::data_loader::Error` describing each skipped line.
+///
+/// # Errors
+///
+/// - `wordlers::data_loader::Error::UnsupportedCompression` if the file uses an unsupported compression.
+/// - `wordlers::data_loader::Error::CorruptedDictionary` or `UnsupportedDictionaryVersion` for an invalid compiled dictionary.
+/// - `std::io::Error` if the file cannot be read or decompressed.
+///
+/// # Examples
+///
+/// ```no_run
+/// use wordlers::data_loader::load_words_file_lenient;
+/// use std::io::Write;
+///
+/// let mut file = tempfile::NamedTempFile::new().unwrap();
+/// write!(file, "apple\nbanana-cherry\npeach").unwrap();
+///
+/// let (word_hashmap, skipped_lines) = load_words_file_lenient(file.path().to_str().unwrap()).unwrap();
+/// assert_eq!(word_hashmap.get(&5).unwrap().len(), 2);
+/// assert_eq!(skipped_lines.len(), 1);
+/// ```
+pub fn load_words_file_lenient(
     data_file_path: &str,
-) -> Result<HashMap<usize, HashSet<String>>, Box<dyn std::error::Error>> {
-    let raw_file = std::fs::read_to_string(data_file_path)?;
-    let lines_with_len: Result<HashSet<(usize, String)>, Box<Error>> = raw_file
-        .lines()
-        .map(|line| {
-            if line.chars().all(char::is_alphabetic) {
-                Ok((line.len(), line.to_uppercase()))
-            } else {
-                Err(Box::new(Error::NotAlphabetic(String::from(data_file_path))))
-            }
-        })
-        .collect();
+) -> Result<(WordHashMap, Vec<Error>), Box<dyn std::error::Error>> {
+    load_words_path(data_file_path, true)
+        .map(|(word_hashmap, report)| (word_hashmap, report.invalid_lines))
+}
+
+/// Loads words from a file like `load_words_file`, or `load_words_file_lenient` if `lenient` is set,
+/// but keeps a compiled dictionary as it is rather than copying its words into a hash map.
+/// This is the way to load the words of a game, where they only need to be looked up and chosen from.
+///
+/// # Returns
+///
+/// The words, and a `wordlers::data_loader::Error` describing each skipped line.
+///
+/// # Errors
+///
+/// As `load_words_file` or `load_words_file_lenient`.
+///
+/// # Examples
+///
+/// ```no_run
+/// use wordlers::data_loader::{load_word_source, LoadedWords};
+/// use wordlers::word_source::WordSource;
+///
+/// let (words, _) = load_word_source("words.dict", false).unwrap();
+/// assert!(matches!(words, LoadedWords::Compiled(_)));
+/// assert!(words.contains("CRANE"));
+/// ```
+pub fn load_word_source(
+    data_file_path: &str,
+    lenient: bool,
+) -> Result<(LoadedWords, Vec<Error>), Box<dyn std::error::Error>> {
+    if let Some(dictionary) = compiled::open_compiled(data_file_path)? {
+        return Ok((LoadedWords::Compiled(dictionary), Vec::new()));
+    }
+    let (word_hashmap, report) =
+        load_words(open_words_file(data_file_path)?, data_file_path, lenient)?;
+    Ok((LoadedWords::List(word_hashmap), report.invalid_lines))
+}
+
+/// Loads words from a file like `load_words_file_lenient`, also reporting duplicate entries.
+///
+/// # Errors
+///
+/// - `wordlers::data_loader::Error::UnsupportedCompression` if the file uses an unsupported compression.
+/// - `wordlers::data_loader::Error::CorruptedDictionary` or `UnsupportedDictionaryVersion` for an invalid compiled dictionary.
+/// - `std::io::Error` if the file cannot be read or decompressed.
+pub fn inspect_words_file(
+    data_file_path: &str,
+) -> Result<(WordHashMap, LoadReport), Box<dyn std::error::Error>> {
+    load_words_path(data_file_path, true)
+}
 
-    match lines_with_len {
-        Ok(lines_with_len) => {
-            Ok(lines_with_len
-                .into_iter()
-                .fold(HashMap::new(), |mut acc, (len, line)| {
-                    acc.entry(len).or_default().insert(line);
-                    acc
-                }))
+/// Counts the occurrences of each character at each position of the `words`.
+///
+/// # Returns
+///
+/// A vector holding, for each position, the count of each character at this position.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+/// use wordlers::data_loader::letter_frequencies_by_position;
+///
+/// let words: HashMap<String, f64> = HashMap::from([(String::from("ABC"), 1.0), (String::from("ABD"), 1.0)]);
+/// let frequencies = letter_frequencies_by_position(&words);
+/// assert_eq!(frequencies[0].get(&'A'), Some(&2));
+/// assert_eq!(frequencies[2].get(&'D'), Some(&1));
+/// ```
+#[must_use]
+pub fn letter_frequencies_by_position<S: BuildHasher>(
+    words: &HashMap<String, f64, S>,
+) -> Vec<HashMap<char, usize>> {
+    let mut frequencies: Vec<HashMap<char, usize>> = Vec::new();
+    for word in words.keys() {
+        for (i, character) in word.chars().enumerate() {
+            if frequencies.len() <= i {
+                frequencies.push(HashMap::new());
+            }
+            *frequencies[i].entry(character).or_insert(0) += 1;
         }
-        Err(err) => Err(err),
     }
+    frequencies
 }
 
-/// Chooses a random word of a specific length from a hashmap of words.
+/// Removes the words whose frequency is lower than `min_frequency`.
+///
+/// Lengths left without any word are removed as well.
+pub fn retain_min_frequency<S: BuildHasher>(
+    word_hashmap: &mut HashMap<usize, HashMap<String, f64, S>, S>,
+    min_frequency: f64,
+) {
+    for words in word_hashmap.values_mut() {
+        words.retain(|_, frequency| *frequency >= min_frequency);
+    }
+    word_hashmap.retain(|_, words| !words.is_empty());
+}
+
+/// Chooses a random word of a specific length from a source of words, see `WordSource::choose_random`.
+///
+/// Words are weighted by their frequency, so that common words are chosen more often.
+/// If every word has a frequency of 0, they are chosen uniformly.
 ///
 /// # Arguments
 ///
-/// * `word_hashmap` - The hashmap containing words organized by their length.
+/// * `words` - The words and their frequency organized by their length, e.g. a `WordHashMap`.
 /// * `word_length` - The length of the word to choose.
 ///
 /// # Returns
@@ -115,41 +478,32 @@ pub fn load_words_file(
 /// # Examples
 ///
 /// ```
-/// use std::collections::{HashMap, HashSet};
+/// use std::collections::HashMap;
 /// use wordlers::data_loader::choose_random_word;
 ///
 /// let mut word_hashmap = HashMap::new();
-/// let words_set_5: HashSet<String> = vec![
-///     String::from("APPLE"),
-///     String::from("PEACH"),
+/// let words_5: HashMap<String, f64> = vec![
+///     (String::from("APPLE"), 10.0),
+///     (String::from("PEACH"), 1.0),
+///     (String::from("XYLYL"), 0.0),
 /// ].into_iter().collect();
 ///
-/// let words_set_6: HashSet<String> = vec![
-///    String::from("BANANA"),
+/// let words_6: HashMap<String, f64> = vec![
+///    (String::from("BANANA"), 1.0),
 /// ].into_iter().collect();
-/// word_hashmap.insert(5, words_set_5.clone());
-/// word_hashmap.insert(6, words_set_6.clone());
+/// word_hashmap.insert(5, words_5.clone());
+/// word_hashmap.insert(6, words_6.clone());
 ///
 /// let result = choose_random_word(&word_hashmap, 5);
 /// assert!(result.is_ok());
 /// let chosen_word = result.unwrap();
-/// assert!(words_set_5.contains(&chosen_word));
+/// assert!(words_5.contains_key(&chosen_word));
 /// assert!(chosen_word.len() == 5);
-/// assert!(!words_set_6.contains(&chosen_word));
+/// assert!(chosen_word != "XYLYL");
+/// assert!(!words_6.contains_key(&chosen_word));
 /// ```
-pub fn choose_random_word<S: BuildHasher>(
-    word_hashmap: &HashMap<usize, HashSet<String, S>, S>,
-    word_length: usize,
-) -> Result<String, Error> {
-    match word_hashmap.get(&word_length) {
-        None => Err(Error::NoWordThisLength(word_length)),
-        Some(words_set) => {
-            match words_set.iter().collect::<Vec<&String>>()[..].choose(&mut rand::thread_rng()) {
-                None => Err(Error::EmptyWordVec(word_length)),
-                Some(word) => Ok((*word).clone()),
-            }
-        }
-    }
+pub fn choose_random_word<W: WordSource>(words: &W, word_length: usize) -> Result<String, Error> {
+    words.choose_random(word_length, &mut rand::thread_rng())
 }
 
 #[cfg(test)]
@@ -179,12 +533,15 @@ mod tests {
             HashMap::from_iter(vec![
                 (
                     5,
-                    vec!["APPLE".to_string(), "PEACH".to_string()]
+                    vec![("APPLE".to_string(), 1.0), ("PEACH".to_string(), 1.0)]
                         .into_iter()
                         .collect()
                 ),
-                (6, vec!["CHERRY".to_string()].into_iter().collect()),
-                (9, vec!["PINEAPPLE".to_string()].into_iter().collect()),
+                (6, vec![("CHERRY".to_string(), 1.0)].into_iter().collect()),
+                (
+                    9,
+                    vec![("PINEAPPLE".to_string(), 1.0)].into_iter().collect()
+                ),
             ])
         );
 
@@ -195,7 +552,7 @@ mod tests {
         let error = result.unwrap_err();
         assert_eq!(
             error.to_string(),
-            format!("File {non_alphabetic_file_path_as_str} contains non-alphabetic characters.")
+            format!("File {non_alphabetic_file_path_as_str} contains non-alphabetic characters at line 2: ['-'].")
         );
 
         // Test case 3: Empty file
@@ -205,23 +562,170 @@ mod tests {
         assert_eq!(word_hashmap.len(), 0);
     }
 
+    #[test]
+    fn test_load_words_file_lenient() {
+        let mut messy_file = tempfile::NamedTempFile::new().unwrap();
+        write!(
+            messy_file,
+            "# Fruits\r\napple  \r\n\r\nbanana-cherry\r\npeach # stone fruit\r\n123\r\nlemon\t?\r\n"
+        )
+        .unwrap();
+        let messy_file_path_as_str = messy_file.path().to_str().unwrap();
+
+        // Test case 1: Blank lines, comments and Windows line endings are ignored, invalid lines are skipped
+        let (word_hashmap, skipped_lines) =
+            load_words_file_lenient(messy_file_path_as_str).unwrap();
+        assert_eq!(
+            word_hashmap,
+            HashMap::from([(
+                5,
+                HashMap::from([("APPLE".to_string(), 1.0), ("PEACH".to_string(), 1.0)])
+            )])
+        );
+        assert_eq!(
+            skipped_lines
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<String>>(),
+            vec![
+                format!("File {messy_file_path_as_str} contains non-alphabetic characters at line 4: ['-']."),
+                format!("File {messy_file_path_as_str} contains non-alphabetic characters at line 6: ['1', '2', '3']."),
+                format!("File {messy_file_path_as_str} contains an invalid frequency at line 7: \"?\"."),
+            ]
+        );
+
+        // Test case 2: Strict loading stops at the first invalid line
+        let error = load_words_file(messy_file_path_as_str).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            format!("File {messy_file_path_as_str} contains non-alphabetic characters at line 4: ['-'].")
+        );
+    }
+
+    #[test]
+    fn test_load_compiled_words_file() {
+        let word_hashmap = WordHashMap::from([(5, HashMap::from([("CRANE".to_string(), 2.0)]))]);
+        let mut compiled_file = tempfile::NamedTempFile::new().unwrap();
+        compiled::write_compiled(&mut compiled_file, &word_hashmap).unwrap();
+
+        // Test case 1: Compiled dictionaries are detected from their magic bytes
+        assert_eq!(
+            load_words_file(compiled_file.path().to_str().unwrap()).unwrap(),
+            word_hashmap
+        );
+
+        // Test case 2: Loaded as a word source, they are used in place
+        let (words, skipped_lines) =
+            load_word_source(compiled_file.path().to_str().unwrap(), false).unwrap();
+        assert!(matches!(words, LoadedWords::Compiled(_)));
+        assert!(skipped_lines.is_empty());
+        assert_eq!(words.frequency("CRANE"), Some(2.0));
+    }
+
+    #[test]
+    fn test_inspect_words_file() {
+        let mut words_file = tempfile::NamedTempFile::new().unwrap();
+        write!(words_file, "crane\ncrate\nCrane\nfoo-bar\ntrace").unwrap();
+        let words_file_path_as_str = words_file.path().to_str().unwrap();
+
+        // Test case 1: Duplicate and invalid lines are reported
+        let (word_hashmap, report) = inspect_words_file(words_file_path_as_str).unwrap();
+        assert_eq!(word_hashmap[&5].len(), 3);
+        assert_eq!(report.duplicate_lines, vec![(3, "CRANE".to_string())]);
+        assert_eq!(report.invalid_lines.len(), 1);
+
+        // Test case 2: Letter frequencies by position
+        let frequencies = letter_frequencies_by_position(&word_hashmap[&5]);
+        assert_eq!(frequencies.len(), 5);
+        assert_eq!(frequencies[0], HashMap::from([('C', 2), ('T', 1)]));
+        assert_eq!(frequencies[4], HashMap::from([('E', 3)]));
+    }
+
+    #[test]
+    fn test_load_words_file_with_frequencies() {
+        let mut frequency_file = tempfile::NamedTempFile::new().unwrap();
+        write!(frequency_file, "apple\t120\npeach,3.5\ncherry\napple\t7").unwrap();
+
+        let mut rank_file = tempfile::NamedTempFile::new().unwrap();
+        write!(rank_file, "word,rank\nhouse,1\nxylyl,4").unwrap();
+
+        let mut invalid_file = tempfile::NamedTempFile::new().unwrap();
+        write!(invalid_file, "apple\t-2").unwrap();
+
+        // Test case 1: Frequencies are stored alongside words, duplicates keep the highest one
+        let mut word_hashmap = load_words_file(frequency_file.path().to_str().unwrap()).unwrap();
+        assert_eq!(word_hashmap.get(&5).unwrap().get("APPLE"), Some(&120.0));
+        assert_eq!(word_hashmap.get(&5).unwrap().get("PEACH"), Some(&3.5));
+        assert_eq!(
+            word_hashmap.get(&6).unwrap().get("CHERRY"),
+            Some(&DEFAULT_FREQUENCY)
+        );
+
+        // Test case 2: Cutoff removes rare words and empty lengths
+        retain_min_frequency(&mut word_hashmap, 2.0);
+        assert_eq!(word_hashmap.get(&5).unwrap().len(), 2);
+        assert!(!word_hashmap.contains_key(&6));
+
+        // Test case 3: Ranks are converted to frequencies
+        let word_hashmap = load_words_file(rank_file.path().to_str().unwrap()).unwrap();
+        assert_eq!(word_hashmap.get(&5).unwrap().get("HOUSE"), Some(&1.0));
+        assert_eq!(word_hashmap.get(&5).unwrap().get("XYLYL"), Some(&0.25));
+
+        // Test case 4: Invalid frequency
+        let invalid_file_path_as_str = invalid_file.path().to_str().unwrap();
+        let error = load_words_file(invalid_file_path_as_str).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            format!(
+                "File {invalid_file_path_as_str} contains an invalid frequency at line 1: \"-2\"."
+            )
+        );
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn test_load_gzip_words_file() {
+        let mut gzip_file = tempfile::Builder::new().suffix(".gz").tempfile().unwrap();
+        let mut encoder =
+            flate2::write::GzEncoder::new(gzip_file.as_file_mut(), flate2::Compression::default());
+        write!(encoder, "apple\npeach\ncherry").unwrap();
+        encoder.finish().unwrap();
+
+        let word_hashmap = load_words_file(gzip_file.path().to_str().unwrap()).unwrap();
+        assert_eq!(word_hashmap.get(&5).unwrap().len(), 2);
+        assert_eq!(word_hashmap.get(&6).unwrap().len(), 1);
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn test_load_zstd_words_file() {
+        // No extension: the format is detected from the magic bytes
+        let mut zstd_file = tempfile::NamedTempFile::new().unwrap();
+        let compressed = zstd::encode_all(&b"apple\npeach\ncherry"[..], 0).unwrap();
+        zstd_file.write_all(&compressed).unwrap();
+
+        let word_hashmap = load_words_file(zstd_file.path().to_str().unwrap()).unwrap();
+        assert_eq!(word_hashmap.get(&5).unwrap().len(), 2);
+        assert_eq!(word_hashmap.get(&6).unwrap().len(), 1);
+    }
+
     #[test]
     fn test_choose_random_word() {
         let mut word_hashmap = HashMap::new();
-        let words_set: HashSet<String> = vec![
-            String::from("apple"),
-            String::from("banana"),
-            String::from("cherry"),
+        let words: HashMap<String, f64> = vec![
+            (String::from("apple"), 1.0),
+            (String::from("banana"), 1.0),
+            (String::from("cherry"), 1.0),
         ]
         .into_iter()
         .collect();
-        word_hashmap.insert(5, words_set.clone());
+        word_hashmap.insert(5, words.clone());
 
         // Test case 1: Word of specified length exists
         let result = choose_random_word(&word_hashmap, 5);
         assert!(result.is_ok());
         let chosen_word = result.unwrap();
-        assert!(words_set.contains(&chosen_word));
+        assert!(words.contains_key(&chosen_word));
 
         // Test case 2: Word of specified length does not exist
         let result = choose_random_word(&word_hashmap, 6);
@@ -231,11 +735,24 @@ mod tests {
 
         // Test case 3: Word vector is empty
         let mut empty_word_hashmap = HashMap::new();
-        let empty_words_set: HashSet<String> = HashSet::new();
-        empty_word_hashmap.insert(5, empty_words_set);
+        let empty_words: HashMap<String, f64> = HashMap::new();
+        empty_word_hashmap.insert(5, empty_words);
         let result = choose_random_word(&empty_word_hashmap, 5);
         assert!(result.is_err());
         let error = result.unwrap_err();
         assert_eq!(error.to_string(), "Vector is empty for length 5.");
+
+        // Test case 4: Words with a frequency of 0 are never chosen among others
+        let mut weighted_word_hashmap = HashMap::new();
+        weighted_word_hashmap.insert(
+            5,
+            HashMap::from([(String::from("HOUSE"), 1.0), (String::from("XYLYL"), 0.0)]),
+        );
+        for _ in 0..20 {
+            assert_eq!(
+                choose_random_word(&weighted_word_hashmap, 5).unwrap(),
+                "HOUSE"
+            );
+        }
     }
 }
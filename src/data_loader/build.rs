@@ -0,0 +1,269 @@
+use super::compiled::open_compiled;
+use super::{load_words, open_words_file, Error, WordHashMap, DEFAULT_FREQUENCY};
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::io::{self, BufRead, Read, Write};
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+/// Filters applied while building a word list.
+#[derive(Debug, Default)]
+pub struct BuildOptions {
+    /// Minimum length of the words, in characters.
+    pub min_length: Option<usize>,
+    /// Maximum length of the words, in characters.
+    pub max_length: Option<usize>,
+    /// Uppercase characters the words must be made of.
+    pub alphabet: Option<Vec<char>>,
+    /// Patterns matched against the normalised words; matching words are dropped.
+    pub exclude_patterns: Vec<Regex>,
+    /// Minimum frequency of the words.
+    pub min_frequency: Option<f64>,
+    /// Normalised words to drop, e.g. profanity.
+    pub blocklist: HashSet<String>,
+    /// Whether to remove the accents of the words, e.g. `É` becoming `E`.
+    pub strip_accents: bool,
+}
+
+/// Normalises a word: composed Unicode form, uppercase, and without accents if `strip_accents` is set.
+///
+/// # Examples
+///
+/// ```
+/// use wordlers::data_loader::build::normalize_word;
+///
+/// assert_eq!(normalize_word("café", false), "CAFÉ");
+/// assert_eq!(normalize_word("cafe\u{301}", true), "CAFE");
+/// ```
+#[must_use]
+pub fn normalize_word(word: &str, strip_accents: bool) -> String {
+    let word: String = if strip_accents {
+        word.nfd().filter(|c| !is_combining_mark(*c)).collect()
+    } else {
+        word.nfc().collect()
+    };
+    word.to_uppercase()
+}
+
+/// Reads the lines of the `inner` reader one at a time, composing their Unicode characters,
+/// so that a whole file is never held in memory.
+struct NfcReader<R: BufRead> {
+    inner: R,
+    line: String,
+    normalized: String,
+    position: usize,
+}
+
+impl<R: BufRead> NfcReader<R> {
+    fn new(inner: R) -> Self {
+        NfcReader {
+            inner,
+            line: String::new(),
+            normalized: String::new(),
+            position: 0,
+        }
+    }
+}
+
+impl<R: BufRead> Read for NfcReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let available = self.fill_buf()?;
+        let length = available.len().min(buf.len());
+        buf[..length].copy_from_slice(&available[..length]);
+        self.consume(length);
+        Ok(length)
+    }
+}
+
+impl<R: BufRead> BufRead for NfcReader<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if self.position == self.normalized.len() {
+            self.line.clear();
+            self.inner.read_line(&mut self.line)?;
+            self.normalized.clear();
+            self.normalized.extend(self.line.nfc());
+            self.position = 0;
+        }
+        Ok(&self.normalized.as_bytes()[self.position..])
+    }
+
+    fn consume(&mut self, amount: usize) {
+        self.position = (self.position + amount).min(self.normalized.len());
+    }
+}
+
+/// Loads a words file like `load_words_file_lenient`, composing the Unicode characters of each line,
+/// so that accents written as combining marks are not rejected.
+/// A compiled dictionary is also accepted, its words being already normalised.
+///
+/// # Returns
+///
+/// The words organized by length, and the errors of the skipped lines.
+///
+/// # Errors
+///
+/// - `wordlers::data_loader::Error::UnsupportedCompression` if the file uses an unsupported compression.
+/// - `wordlers::data_loader::Error` if the file is a corrupted or unsupported compiled dictionary.
+/// - `std::io::Error` if the file cannot be read or decompressed.
+pub fn load_normalized_words_file(
+    data_file_path: &str,
+) -> Result<(WordHashMap, Vec<Error>), Box<dyn std::error::Error>> {
+    if let Some(dictionary) = open_compiled(data_file_path)? {
+        return Ok((dictionary.to_word_hashmap(), Vec::new()));
+    }
+    let reader = NfcReader::new(open_words_file(data_file_path)?);
+    load_words(reader, data_file_path, true)
+        .map(|(word_hashmap, report)| (word_hashmap, report.invalid_lines))
+}
+
+/// Merges word lists, normalising and filtering their words.
+///
+/// A word listed several times, possibly with different cases or accents, is kept once with its highest frequency.
+///
+/// # Returns
+///
+/// The words and their frequencies, sorted alphabetically.
+#[must_use]
+pub fn build_word_list(inputs: &[WordHashMap], options: &BuildOptions) -> Vec<(String, f64)> {
+    let mut merged: HashMap<String, f64> = HashMap::new();
+    for word_hashmap in inputs {
+        for (word, frequency) in word_hashmap.values().flatten() {
+            let word = normalize_word(word, options.strip_accents);
+            let known_frequency = merged.entry(word).or_insert(*frequency);
+            *known_frequency = known_frequency.max(*frequency);
+        }
+    }
+
+    let mut words: Vec<(String, f64)> = merged
+        .into_iter()
+        .filter(|(word, frequency)| {
+            let length = word.chars().count();
+            options.min_length.is_none_or(|min| length >= min)
+                && options.max_length.is_none_or(|max| length <= max)
+                && options
+                    .alphabet
+                    .as_ref()
+                    .is_none_or(|alphabet| word.chars().all(|c| alphabet.contains(&c)))
+                && options.min_frequency.is_none_or(|min| *frequency >= min)
+                && !options.blocklist.contains(word)
+                && !options
+                    .exclude_patterns
+                    .iter()
+                    .any(|pattern| pattern.is_match(word))
+        })
+        .collect();
+    words.sort_by(|(word_a, _), (word_b, _)| word_a.cmp(word_b));
+    words
+}
+
+/// Writes a word list in the format read by `load_words_file`, one lowercase word per line.
+///
+/// The frequencies are written in a second column, under a `word,frequency` header,
+/// unless all of them are `DEFAULT_FREQUENCY`.
+///
+/// # Errors
+///
+/// Returns an error if the `writer` fails.
+pub fn write_word_list(mut writer: impl Write, words: &[(String, f64)]) -> io::Result<()> {
+    let with_frequencies = words
+        .iter()
+        .any(|(_, frequency)| *frequency != DEFAULT_FREQUENCY);
+    if with_frequencies {
+        writeln!(writer, "word,frequency")?;
+    }
+    for (word, frequency) in words {
+        if with_frequencies {
+            writeln!(writer, "{},{frequency}", word.to_lowercase())?;
+        } else {
+            writeln!(writer, "{}", word.to_lowercase())?;
+        }
+    }
+    writer.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_word_list() {
+        let first = WordHashMap::from([
+            (
+                4,
+                HashMap::from([("CAFÉ".to_string(), 2.0), ("DARN".to_string(), 1.0)]),
+            ),
+            (5, HashMap::from([("CRANE".to_string(), 0.5)])),
+        ]);
+        let second = WordHashMap::from([
+            (4, HashMap::from([("CAFE".to_string(), 3.0)])),
+            (6, HashMap::from([("PLANET".to_string(), 1.0)])),
+        ]);
+
+        // Test case 1: Accents are stripped and duplicates merged with their highest frequency
+        let options = BuildOptions {
+            strip_accents: true,
+            ..BuildOptions::default()
+        };
+        assert_eq!(
+            build_word_list(&[first.clone(), second.clone()], &options),
+            vec![
+                ("CAFE".to_string(), 3.0),
+                ("CRANE".to_string(), 0.5),
+                ("DARN".to_string(), 1.0),
+                ("PLANET".to_string(), 1.0),
+            ]
+        );
+
+        // Test case 2: Filters
+        let options = BuildOptions {
+            max_length: Some(5),
+            alphabet: Some(('A'..='Z').collect()),
+            exclude_patterns: vec![Regex::new("^CR").unwrap()],
+            min_frequency: Some(1.0),
+            blocklist: HashSet::from(["DARN".to_string()]),
+            ..BuildOptions::default()
+        };
+        assert_eq!(
+            build_word_list(&[first, second], &options),
+            vec![("CAFE".to_string(), 3.0)]
+        );
+    }
+
+    #[test]
+    fn test_nfc_reader() {
+        // Test case 1: Each line is composed, and an empty input reads as nothing
+        let mut content = String::new();
+        NfcReader::new("cafe\u{301}\nnai\u{308}ve\n".as_bytes())
+            .read_to_string(&mut content)
+            .unwrap();
+        assert_eq!(content, "café\nnaïve\n");
+        assert_eq!(
+            NfcReader::new("".as_bytes())
+                .lines()
+                .collect::<io::Result<Vec<String>>>()
+                .unwrap(),
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    fn test_write_word_list() {
+        let mut output = Vec::new();
+
+        // Test case 1: Plain list
+        write_word_list(&mut output, &[("CRANE".to_string(), DEFAULT_FREQUENCY)]).unwrap();
+        assert_eq!(String::from_utf8(output.clone()).unwrap(), "crane\n");
+
+        // Test case 2: List with frequencies
+        output.clear();
+        write_word_list(
+            &mut output,
+            &[("CRANE".to_string(), 0.5), ("CRATE".to_string(), 2.0)],
+        )
+        .unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "word,frequency\ncrane,0.5\ncrate,2\n"
+        );
+    }
+}
@@ -0,0 +1,130 @@
+use super::language_pack::LanguagePack;
+use super::{load_words, WordHashMap};
+use flate2::bufread::DeflateDecoder;
+use std::io::BufReader;
+
+/// A language pack embedded in the binary, its word lists being compressed at build time.
+pub struct BuiltinDictionary {
+    /// The code used to select the dictionary.
+    pub name: &'static str,
+    description: &'static str,
+    messages: &'static str,
+    answers: &'static [u8],
+    guesses: Option<&'static [u8]>,
+}
+
+macro_rules! builtin_dictionary {
+    ($name:literal) => {
+        builtin_dictionary!($name, None)
+    };
+    ($name:literal, guesses) => {
+        builtin_dictionary!(
+            $name,
+            Some(include_bytes!(concat!(
+                env!("OUT_DIR"),
+                "/",
+                $name,
+                "_guesses.txt.deflate"
+            )))
+        )
+    };
+    ($name:literal, $guesses:expr) => {
+        BuiltinDictionary {
+            name: $name,
+            description: include_str!(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/data/",
+                $name,
+                "/pack.txt"
+            )),
+            messages: include_str!(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/data/",
+                $name,
+                "/messages.txt"
+            )),
+            answers: include_bytes!(concat!(env!("OUT_DIR"), "/", $name, "_answers.txt.deflate")),
+            guesses: $guesses,
+        }
+    };
+}
+
+// Generated by `build.rs` from the packs of the `data` directory.
+include!(concat!(env!("OUT_DIR"), "/builtin_dictionaries.rs"));
+
+impl BuiltinDictionary {
+    /// Returns the words that can be chosen as the word to guess.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the embedded list is invalid, which is checked by the tests.
+    #[must_use]
+    pub fn answers(&self) -> WordHashMap {
+        decompress(self.name, self.answers)
+    }
+
+    /// Returns the words only accepted as guesses, in addition to the answers.
+    /// It is empty for the packs shipping answers only.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the embedded list is invalid, which is checked by the tests.
+    #[must_use]
+    pub fn guesses(&self) -> WordHashMap {
+        self.guesses
+            .map_or_else(WordHashMap::new, |guesses| decompress(self.name, guesses))
+    }
+
+    /// Returns the complete language pack of the dictionary.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the embedded pack is invalid, which is checked by the tests.
+    #[must_use]
+    pub fn language_pack(&self) -> LanguagePack {
+        match LanguagePack::from_parts(
+            self.name,
+            self.description,
+            self.messages,
+            self.answers(),
+            self.guesses(),
+        ) {
+            Ok(language_pack) => language_pack,
+            Err(err) => panic!("Invalid builtin language pack {}: {err}", self.name),
+        }
+    }
+}
+
+fn decompress(name: &str, compressed: &'static [u8]) -> WordHashMap {
+    let reader = BufReader::new(DeflateDecoder::new(compressed));
+    match load_words(reader, &format!("builtin:{name}"), false) {
+        Ok((word_hashmap, _)) => word_hashmap,
+        Err(err) => panic!("Invalid builtin dictionary {name}: {err}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_dictionaries() {
+        for dictionary in &BUILTIN_DICTIONARIES {
+            let language_pack = dictionary.language_pack();
+            assert!(!language_pack.answers.get(&5).unwrap().is_empty());
+            let guesses_path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("data")
+                .join(dictionary.name)
+                .join("guesses.txt");
+            assert_eq!(language_pack.guesses.is_empty(), !guesses_path.is_file());
+            for (length, words) in &language_pack.guesses {
+                if let Some(answers) = language_pack.answers.get(length) {
+                    assert!(words.keys().all(|word| !answers.contains_key(word)));
+                }
+            }
+            for row in &language_pack.keyboard {
+                assert!(row.iter().all(|c| language_pack.alphabet.contains(c)));
+            }
+        }
+    }
+}
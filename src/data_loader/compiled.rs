@@ -0,0 +1,421 @@
+use super::{Error, WordHashMap};
+use std::collections::{BTreeSet, HashMap};
+use std::fs::File;
+use std::io::{self, Read, Write};
+use std::path::Path;
+
+/// Magic bytes starting every compiled dictionary.
+pub const COMPILED_MAGIC: [u8; 8] = *b"WRDLDICT";
+/// Version of the compiled dictionary format written by this build.
+pub const COMPILED_VERSION: u32 = 1;
+
+/// Size of the header: magic bytes, version and checksum.
+const HEADER_SIZE: usize = 20;
+/// Size of an entry of the bucket table: word length, word count and offset of the words.
+const BUCKET_ENTRY_SIZE: usize = 16;
+/// Size of a frequency, stored as a little-endian `f64`.
+const FREQUENCY_SIZE: usize = 8;
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+/// Computes the 64-bit FNV-1a hash of `bytes`.
+fn checksum(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(FNV_OFFSET_BASIS, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(FNV_PRIME)
+    })
+}
+
+fn read_u32(bytes: &[u8], position: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(
+        bytes.get(position..position + 4)?.try_into().ok()?,
+    ))
+}
+
+fn read_u64(bytes: &[u8], position: usize) -> Option<u64> {
+    Some(u64::from_le_bytes(
+        bytes.get(position..position + 8)?.try_into().ok()?,
+    ))
+}
+
+/// Checks whether `bytes` start like a compiled dictionary.
+#[must_use]
+pub fn is_compiled(bytes: &[u8]) -> bool {
+    bytes.starts_with(&COMPILED_MAGIC)
+}
+
+/// Compiles words into the binary dictionary format.
+///
+/// The format is made of:
+/// - a header: `COMPILED_MAGIC`, the format version, and the FNV-1a checksum of the rest of the file;
+/// - the alphabet: the number of characters, then each character, sorted;
+/// - the bucket table: the number of buckets, then for each word length, the word count and the offset of the words;
+/// - for each bucket, the sorted words, each character packed as its one-byte index in the alphabet,
+///   followed by their frequencies.
+///
+/// Integers and frequencies are stored little-endian.
+///
+/// # Errors
+///
+/// Returns `wordlers::data_loader::Error::AlphabetTooLarge` if the words use more than 256 distinct characters.
+pub fn compile(word_hashmap: &WordHashMap) -> Result<Vec<u8>, Error> {
+    let alphabet: Vec<char> = word_hashmap
+        .values()
+        .flat_map(HashMap::keys)
+        .flat_map(|word| word.chars())
+        .collect::<BTreeSet<char>>()
+        .into_iter()
+        .collect();
+    if alphabet.len() > usize::from(u8::MAX) + 1 {
+        return Err(Error::AlphabetTooLarge(alphabet.len()));
+    }
+    let indices: HashMap<char, u8> = alphabet
+        .iter()
+        .zip(0..=u8::MAX)
+        .map(|(character, index)| (*character, index))
+        .collect();
+
+    let mut lengths: Vec<&usize> = word_hashmap
+        .iter()
+        .filter(|(_, words)| !words.is_empty())
+        .map(|(length, _)| length)
+        .collect();
+    lengths.sort();
+
+    let mut body = Vec::new();
+    body.extend((alphabet.len() as u32).to_le_bytes());
+    for character in &alphabet {
+        body.extend(u32::from(*character).to_le_bytes());
+    }
+    body.extend((lengths.len() as u32).to_le_bytes());
+    let table_position = body.len();
+    body.resize(table_position + lengths.len() * BUCKET_ENTRY_SIZE, 0);
+
+    for (i, length) in lengths.iter().enumerate() {
+        let mut words: Vec<(Vec<u8>, f64)> = word_hashmap[length]
+            .iter()
+            .map(|(word, frequency)| (word.chars().map(|c| indices[&c]).collect(), *frequency))
+            .collect();
+        words.sort_by(|(word_a, _), (word_b, _)| word_a.cmp(word_b));
+
+        let entry = table_position + i * BUCKET_ENTRY_SIZE;
+        let offset = (HEADER_SIZE + body.len()) as u64;
+        body[entry..entry + 4].copy_from_slice(&(**length as u32).to_le_bytes());
+        body[entry + 4..entry + 8].copy_from_slice(&(words.len() as u32).to_le_bytes());
+        body[entry + 8..entry + 16].copy_from_slice(&offset.to_le_bytes());
+        for (word, _) in &words {
+            body.extend(word);
+        }
+        for (_, frequency) in &words {
+            body.extend(frequency.to_le_bytes());
+        }
+    }
+
+    let mut bytes = Vec::with_capacity(HEADER_SIZE + body.len());
+    bytes.extend(COMPILED_MAGIC);
+    bytes.extend(COMPILED_VERSION.to_le_bytes());
+    bytes.extend(checksum(&body).to_le_bytes());
+    bytes.extend(body);
+    Ok(bytes)
+}
+
+/// Compiles words and writes them to `writer`, see `compile`.
+///
+/// # Errors
+///
+/// - `wordlers::data_loader::Error::AlphabetTooLarge` if the words use more than 256 distinct characters.
+/// - `std::io::Error` if the `writer` fails.
+pub fn write_compiled(
+    mut writer: impl Write,
+    word_hashmap: &WordHashMap,
+) -> Result<(), Box<dyn std::error::Error>> {
+    writer.write_all(&compile(word_hashmap)?)?;
+    writer.flush()?;
+    Ok(())
+}
+
+/// The words of a given length in a compiled dictionary.
+#[derive(Debug)]
+struct Bucket {
+    length: usize,
+    count: usize,
+    offset: usize,
+}
+
+/// A dictionary in the binary format written by `compile`.
+///
+/// Words are looked up by binary search directly in the `bytes`, without building a `String` per word.
+/// The bytes may be owned, e.g. read from a file, or borrowed, e.g. embedded with `include_bytes!`.
+#[derive(Debug)]
+pub struct CompiledDictionary<B: AsRef<[u8]>> {
+    bytes: B,
+    alphabet: Vec<char>,
+    buckets: Vec<Bucket>,
+}
+
+impl<B: AsRef<[u8]>> CompiledDictionary<B> {
+    /// Checks and indexes a compiled dictionary. `data_file_path` only names the source in errors.
+    ///
+    /// # Errors
+    ///
+    /// - `wordlers::data_loader::Error::UnsupportedDictionaryVersion` if the dictionary was written by an incompatible build.
+    /// - `wordlers::data_loader::Error::CorruptedDictionary` if the bytes are not a valid compiled dictionary.
+    pub fn from_bytes(bytes: B, data_file_path: &str) -> Result<Self, Error> {
+        let corrupted = || Error::CorruptedDictionary(String::from(data_file_path));
+        let data = bytes.as_ref();
+        if !is_compiled(data) || data.len() < HEADER_SIZE {
+            return Err(corrupted());
+        }
+        let version = read_u32(data, 8).ok_or_else(corrupted)?;
+        if version != COMPILED_VERSION {
+            return Err(Error::UnsupportedDictionaryVersion(
+                String::from(data_file_path),
+                version,
+            ));
+        }
+        if read_u64(data, 12) != Some(checksum(&data[HEADER_SIZE..])) {
+            return Err(corrupted());
+        }
+
+        let mut position = HEADER_SIZE;
+        let alphabet_len = read_u32(data, position).ok_or_else(corrupted)? as usize;
+        position += 4;
+        let mut alphabet = Vec::with_capacity(alphabet_len.min(usize::from(u8::MAX) + 1));
+        for _ in 0..alphabet_len {
+            let code = read_u32(data, position).ok_or_else(corrupted)?;
+            alphabet.push(char::from_u32(code).ok_or_else(corrupted)?);
+            position += 4;
+        }
+
+        let bucket_count = read_u32(data, position).ok_or_else(corrupted)? as usize;
+        position += 4;
+        let mut buckets = Vec::new();
+        for _ in 0..bucket_count {
+            let bucket = Bucket {
+                length: read_u32(data, position).ok_or_else(corrupted)? as usize,
+                count: read_u32(data, position + 4).ok_or_else(corrupted)? as usize,
+                offset: usize::try_from(read_u64(data, position + 8).ok_or_else(corrupted)?)
+                    .map_err(|_| corrupted())?,
+            };
+            position += BUCKET_ENTRY_SIZE;
+            let size = bucket
+                .count
+                .checked_mul(bucket.length + FREQUENCY_SIZE)
+                .ok_or_else(corrupted)?;
+            let words = data
+                .get(bucket.offset..bucket.offset.checked_add(size).ok_or_else(corrupted)?)
+                .ok_or_else(corrupted)?;
+            if words[..bucket.count * bucket.length]
+                .iter()
+                .any(|index| usize::from(*index) >= alphabet.len())
+            {
+                return Err(corrupted());
+            }
+            buckets.push(bucket);
+        }
+
+        Ok(CompiledDictionary {
+            bytes,
+            alphabet,
+            buckets,
+        })
+    }
+
+    /// Returns the sorted characters the words are made of.
+    #[must_use]
+    pub fn alphabet(&self) -> &[char] {
+        &self.alphabet
+    }
+
+    /// Returns the lengths of the words, in increasing order.
+    pub fn lengths(&self) -> impl Iterator<Item = usize> + '_ {
+        self.buckets.iter().map(|bucket| bucket.length)
+    }
+
+    /// Returns the number of words of `length` characters.
+    #[must_use]
+    pub fn count(&self, length: usize) -> usize {
+        self.bucket(length).map_or(0, |bucket| bucket.count)
+    }
+
+    fn bucket(&self, length: usize) -> Option<&Bucket> {
+        self.buckets.iter().find(|bucket| bucket.length == length)
+    }
+
+    /// Returns the packed word at `index` of the `bucket`.
+    fn packed_word(&self, bucket: &Bucket, index: usize) -> &[u8] {
+        let start = bucket.offset + index * bucket.length;
+        &self.bytes.as_ref()[start..start + bucket.length]
+    }
+
+    fn frequency_at(&self, bucket: &Bucket, index: usize) -> f64 {
+        let start = bucket.offset + bucket.count * bucket.length + index * FREQUENCY_SIZE;
+        f64::from_le_bytes(
+            self.bytes.as_ref()[start..start + FREQUENCY_SIZE]
+                .try_into()
+                .expect("bucket bounds are checked when loading"),
+        )
+    }
+
+    fn decode(&self, packed_word: &[u8]) -> String {
+        packed_word
+            .iter()
+            .map(|index| self.alphabet[usize::from(*index)])
+            .collect()
+    }
+
+    /// Returns the frequency of the uppercased `word`, or `None` if it is not in the dictionary.
+    #[must_use]
+    pub fn frequency(&self, word: &str) -> Option<f64> {
+        let bucket = self.bucket(word.chars().count())?;
+        let packed_word = word
+            .chars()
+            .map(|c| {
+                self.alphabet
+                    .binary_search(&c)
+                    .ok()
+                    .and_then(|index| u8::try_from(index).ok())
+            })
+            .collect::<Option<Vec<u8>>>()?;
+
+        let (mut low, mut high) = (0, bucket.count);
+        while low < high {
+            let middle = low + (high - low) / 2;
+            match self.packed_word(bucket, middle).cmp(&packed_word) {
+                std::cmp::Ordering::Less => low = middle + 1,
+                std::cmp::Ordering::Greater => high = middle,
+                std::cmp::Ordering::Equal => return Some(self.frequency_at(bucket, middle)),
+            }
+        }
+        None
+    }
+
+    /// Checks whether the uppercased `word` is in the dictionary.
+    #[must_use]
+    pub fn contains(&self, word: &str) -> bool {
+        self.frequency(word).is_some()
+    }
+
+    /// Returns the words of `length` characters with their frequencies, sorted alphabetically.
+    pub fn words(&self, length: usize) -> impl Iterator<Item = (String, f64)> + '_ {
+        self.bucket(length).into_iter().flat_map(move |bucket| {
+            (0..bucket.count).map(move |index| {
+                (
+                    self.decode(self.packed_word(bucket, index)),
+                    self.frequency_at(bucket, index),
+                )
+            })
+        })
+    }
+
+    /// Returns the frequencies of the words of `length` characters, in the order of `CompiledDictionary::words`.
+    pub fn frequencies(&self, length: usize) -> impl Iterator<Item = f64> + '_ {
+        self.bucket(length).into_iter().flat_map(move |bucket| {
+            (0..bucket.count).map(move |index| self.frequency_at(bucket, index))
+        })
+    }
+
+    /// Returns the word at `index` among the words of `length` characters, sorted alphabetically,
+    /// or `None` if there is no such word.
+    #[must_use]
+    pub fn word(&self, length: usize, index: usize) -> Option<String> {
+        let bucket = self.bucket(length).filter(|bucket| index < bucket.count)?;
+        Some(self.decode(self.packed_word(bucket, index)))
+    }
+
+    /// Converts the dictionary to words organized by length.
+    #[must_use]
+    pub fn to_word_hashmap(&self) -> WordHashMap {
+        self.lengths()
+            .map(|length| (length, self.words(length).collect()))
+            .collect()
+    }
+}
+
+/// Reads a compiled dictionary file. The whole file is read into memory once,
+/// the words being then looked up in these bytes without further copies.
+///
+/// # Returns
+///
+/// `None` if the file is not a compiled dictionary, the dictionary otherwise.
+///
+/// # Errors
+///
+/// - `wordlers::data_loader::Error` if the file is a corrupted or unsupported compiled dictionary.
+/// - `std::io::Error` if the file cannot be read.
+pub fn open_compiled(
+    data_file_path: &str,
+) -> Result<Option<CompiledDictionary<Vec<u8>>>, Box<dyn std::error::Error>> {
+    let mut file = File::open(Path::new(data_file_path))?;
+    let mut magic = [0; COMPILED_MAGIC.len()];
+    match file.read_exact(&mut magic) {
+        Ok(()) if is_compiled(&magic) => {}
+        Ok(()) => return Ok(None),
+        Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => return Ok(None),
+        Err(err) => return Err(Box::new(err)),
+    }
+
+    let mut bytes = magic.to_vec();
+    file.read_to_end(&mut bytes)?;
+    Ok(Some(CompiledDictionary::from_bytes(bytes, data_file_path)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compiled_dictionary() {
+        let word_hashmap = WordHashMap::from([
+            (
+                5,
+                HashMap::from([
+                    ("CRANE".to_string(), 2.0),
+                    ("APPLE".to_string(), 1.0),
+                    ("ÉCRAN".to_string(), 0.5),
+                ]),
+            ),
+            (3, HashMap::from([("CAT".to_string(), 1.0)])),
+        ]);
+        let bytes = compile(&word_hashmap).unwrap();
+
+        // Test case 1: Lookups by binary search
+        let dictionary = CompiledDictionary::from_bytes(bytes.as_slice(), "test").unwrap();
+        assert_eq!(dictionary.lengths().collect::<Vec<usize>>(), vec![3, 5]);
+        assert_eq!(dictionary.count(5), 3);
+        assert_eq!(dictionary.frequency("CRANE"), Some(2.0));
+        assert!(dictionary.contains("ÉCRAN"));
+        assert!(!dictionary.contains("CRATE"));
+        assert!(!dictionary.contains("ZEBRA"));
+        assert_eq!(
+            dictionary
+                .words(5)
+                .map(|(word, _)| word)
+                .collect::<Vec<String>>(),
+            vec!["APPLE", "CRANE", "ÉCRAN"]
+        );
+
+        assert_eq!(dictionary.word(5, 1), Some(String::from("CRANE")));
+        assert_eq!(dictionary.word(5, 3), None);
+        assert_eq!(
+            dictionary.frequencies(5).collect::<Vec<f64>>(),
+            vec![1.0, 2.0, 0.5]
+        );
+
+        // Test case 2: Round trip
+        assert_eq!(dictionary.to_word_hashmap(), word_hashmap);
+
+        // Test case 3: Corrupted bytes are detected by the checksum
+        let mut corrupted = bytes.clone();
+        *corrupted.last_mut().unwrap() ^= 1;
+        assert!(matches!(
+            CompiledDictionary::from_bytes(corrupted, "test"),
+            Err(Error::CorruptedDictionary(_))
+        ));
+
+        // Test case 4: Truncated bytes
+        assert!(matches!(
+            CompiledDictionary::from_bytes(&bytes[..10], "test"),
+            Err(Error::CorruptedDictionary(_))
+        ));
+    }
+}
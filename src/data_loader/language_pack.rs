@@ -0,0 +1,363 @@
+use super::{load_words_file, WordHashMap};
+use crate::messages::{message, MessageId};
+use core::fmt;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+
+/// Name of the file describing a language pack in its directory.
+pub const PACK_FILE: &str = "pack.txt";
+/// Name of the file holding the words that can be chosen as the word to guess.
+pub const ANSWERS_FILE: &str = "answers.txt";
+/// Name of the optional file holding the words only accepted as guesses.
+pub const GUESSES_FILE: &str = "guesses.txt";
+/// Name of the optional file holding the localised user interface messages.
+pub const MESSAGES_FILE: &str = "messages.txt";
+
+const DEFAULT_KEYBOARD_ROW_LENGTH: usize = 10;
+
+/// Custom error type for language pack errors.
+#[derive(Debug)]
+pub enum Error {
+    /// Error indicating that no language pack has this code. (code, available codes)
+    UnknownLanguage(String, Vec<String>),
+    /// Error indicating that a language pack description lacks a mandatory key. (path, key)
+    MissingKey(String, &'static str),
+    /// Error indicating that a line is not a `key = value` pair. (path, line number)
+    MalformedLine(String, usize),
+    /// Error indicating that a word contains characters outside of the pack alphabet. (pack code, word)
+    OutsideAlphabet(String, String),
+}
+
+impl std::error::Error for Error {}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            Error::UnknownLanguage(code, available) => message(
+                MessageId::LanguagePackUnknownLanguage,
+                &[("code", code), ("available", &available.join(", "))],
+            ),
+            Error::MissingKey(path, key) => message(
+                MessageId::LanguagePackMissingKey,
+                &[("path", path), ("key", key)],
+            ),
+            Error::MalformedLine(path, line_number) => message(
+                MessageId::LanguagePackMalformedLine,
+                &[("path", path), ("line", line_number)],
+            ),
+            Error::OutsideAlphabet(code, word) => message(
+                MessageId::LanguagePackOutsideAlphabet,
+                &[("word", word), ("code", code)],
+            ),
+        };
+        write!(f, "{text}")
+    }
+}
+
+/// Parses `key = value` lines, ignoring blank lines and `#` comments.
+///
+/// `path` only names the source in errors.
+///
+/// # Errors
+///
+/// Returns `Error::MalformedLine` if a line is not a `key = value` pair.
+pub fn parse_key_values(path: &str, content: &str) -> Result<HashMap<String, String>, Error> {
+    let mut key_values = HashMap::new();
+    for (i, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let Some((key, value)) = line.split_once('=') else {
+            return Err(Error::MalformedLine(String::from(path), i + 1));
+        };
+        key_values.insert(String::from(key.trim()), String::from(value.trim()));
+    }
+    Ok(key_values)
+}
+
+/// A language: its word lists, alphabet, keyboard layout and user interface messages.
+///
+/// A pack is stored in a directory named after its code (e.g. `fr`) containing:
+/// - `pack.txt`: `key = value` lines with the `name` of the language, its `alphabet`
+///   and optionally its `keyboard` layout, rows being separated by spaces.
+/// - `answers.txt`: the words that can be chosen as the word to guess, in the format of `load_words_file`.
+/// - `guesses.txt` (optional): the words only accepted as guesses.
+/// - `messages.txt` (optional): the message catalogue of the language, as `message.id = template` lines
+///   (see `wordlers::messages`), selected with the user interface language.
+#[derive(Debug)]
+pub struct LanguagePack {
+    /// The code used to select the pack, e.g. `fr`.
+    pub code: String,
+    /// The name of the language, in that language.
+    pub name: String,
+    /// The uppercase characters words are made of.
+    pub alphabet: Vec<char>,
+    /// The rows of the keyboard, used by the on-screen keyboard.
+    pub keyboard: Vec<Vec<char>>,
+    /// The message catalogue of the language, by message ID.
+    pub messages: HashMap<String, String>,
+    /// The words that can be chosen as the word to guess.
+    pub answers: WordHashMap,
+    /// The words only accepted as guesses, in addition to the answers.
+    pub guesses: WordHashMap,
+}
+
+impl Default for LanguagePack {
+    /// An English pack without words, used when no pack is available.
+    fn default() -> Self {
+        LanguagePack {
+            code: String::from("en"),
+            name: String::from("English"),
+            alphabet: ('A'..='Z').collect(),
+            keyboard: ["QWERTYUIOP", "ASDFGHJKL", "ZXCVBNM"]
+                .iter()
+                .map(|row| row.chars().collect())
+                .collect(),
+            messages: HashMap::new(),
+            answers: WordHashMap::new(),
+            guesses: WordHashMap::new(),
+        }
+    }
+}
+
+impl LanguagePack {
+    /// Builds a language pack from the content of its files.
+    ///
+    /// # Errors
+    ///
+    /// - `wordlers::data_loader::language_pack::Error::MalformedLine` if `description` or `messages` are malformed.
+    /// - `wordlers::data_loader::language_pack::Error::MissingKey` if `description` lacks the `name` or `alphabet`.
+    /// - `wordlers::data_loader::language_pack::Error::OutsideAlphabet` if a word is not made of the alphabet.
+    pub fn from_parts(
+        code: &str,
+        description: &str,
+        messages: &str,
+        answers: WordHashMap,
+        guesses: WordHashMap,
+    ) -> Result<Self, Error> {
+        let description_path = format!("{code}/{PACK_FILE}");
+        let mut description = parse_key_values(&description_path, description)?;
+        let messages = parse_key_values(&format!("{code}/{MESSAGES_FILE}"), messages)?;
+
+        let name = description
+            .remove("name")
+            .ok_or(Error::MissingKey(description_path.clone(), "name"))?;
+        let alphabet: Vec<char> = description
+            .remove("alphabet")
+            .ok_or(Error::MissingKey(description_path, "alphabet"))?
+            .to_uppercase()
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect();
+        let keyboard = match description.remove("keyboard") {
+            Some(keyboard) => keyboard
+                .to_uppercase()
+                .split_whitespace()
+                .map(|row| row.chars().collect())
+                .collect(),
+            None => alphabet
+                .chunks(DEFAULT_KEYBOARD_ROW_LENGTH)
+                .map(<[char]>::to_vec)
+                .collect(),
+        };
+
+        for word in answers
+            .values()
+            .chain(guesses.values())
+            .flat_map(HashMap::keys)
+        {
+            if !word.chars().all(|c| alphabet.contains(&c)) {
+                return Err(Error::OutsideAlphabet(String::from(code), word.clone()));
+            }
+        }
+
+        Ok(LanguagePack {
+            code: String::from(code),
+            name,
+            alphabet,
+            keyboard,
+            messages,
+            answers,
+            guesses,
+        })
+    }
+
+    /// Loads a language pack from its directory, the pack code being the directory name.
+    ///
+    /// # Errors
+    ///
+    /// - `wordlers::data_loader::language_pack::Error` if the pack is invalid.
+    /// - `wordlers::data_loader::Error` if a word list is invalid.
+    /// - `std::io::Error` if a file cannot be read.
+    pub fn load_dir(pack_path: &Path) -> Result<Self, Box<dyn std::error::Error>> {
+        let code = pack_path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let description = std::fs::read_to_string(pack_path.join(PACK_FILE))?;
+        let messages_path = pack_path.join(MESSAGES_FILE);
+        let messages = if messages_path.exists() {
+            std::fs::read_to_string(messages_path)?
+        } else {
+            String::new()
+        };
+        let answers = load_words_file(&pack_path.join(ANSWERS_FILE).to_string_lossy())?;
+        let guesses_path = pack_path.join(GUESSES_FILE);
+        let guesses = if guesses_path.exists() {
+            load_words_file(&guesses_path.to_string_lossy())?
+        } else {
+            WordHashMap::new()
+        };
+
+        Ok(LanguagePack::from_parts(
+            &code,
+            &description,
+            &messages,
+            answers,
+            guesses,
+        )?)
+    }
+}
+
+/// Returns the directories searched for user language packs:
+/// `$WORDLERS_LANG_DIR` if set, otherwise `$HOME/.local/share/wordlers/lang`.
+#[must_use]
+pub fn default_lang_dirs() -> Vec<PathBuf> {
+    if let Some(lang_dir) = std::env::var_os("WORDLERS_LANG_DIR") {
+        return vec![PathBuf::from(lang_dir)];
+    }
+    std::env::var_os("HOME")
+        .map(|home| vec![Path::new(&home).join(".local/share/wordlers/lang")])
+        .unwrap_or_default()
+}
+
+/// Finds the messages of the user language pack with the given `code` in `lang_dirs`,
+/// without loading its word lists.
+///
+/// # Returns
+/// `None` if there is no such pack, or if its messages are missing or malformed.
+#[must_use]
+pub fn find_messages(code: &str, lang_dirs: &[PathBuf]) -> Option<HashMap<String, String>> {
+    lang_dirs.iter().find_map(|lang_dir| {
+        let messages_path = lang_dir.join(code).join(MESSAGES_FILE);
+        let messages = std::fs::read_to_string(&messages_path).ok()?;
+        parse_key_values(&messages_path.to_string_lossy(), &messages).ok()
+    })
+}
+
+/// Returns the codes of the language packs found in `lang_dirs` and embedded in the binary, sorted.
+#[must_use]
+pub fn available_language_packs(lang_dirs: &[PathBuf]) -> Vec<String> {
+    let mut codes: Vec<String> = lang_dirs
+        .iter()
+        .filter_map(|lang_dir| std::fs::read_dir(lang_dir).ok())
+        .flatten()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.path().join(PACK_FILE).is_file())
+        .map(|entry| entry.file_name().to_string_lossy().into_owned())
+        .collect();
+    #[cfg(feature = "builtin-dictionary")]
+    codes.extend(
+        super::builtin::BUILTIN_DICTIONARIES
+            .iter()
+            .map(|dictionary| String::from(dictionary.name)),
+    );
+    codes.sort();
+    codes.dedup();
+    codes
+}
+
+/// Finds the language pack with the given `code`.
+///
+/// Packs in `lang_dirs` take precedence over the ones embedded in the binary,
+/// so that users can drop in their own packs or override the builtin ones.
+/// Without any pack, `en` falls back to `LanguagePack::default`.
+///
+/// # Errors
+///
+/// - `wordlers::data_loader::language_pack::Error::UnknownLanguage` if no pack has this code.
+/// - Any error of `LanguagePack::load_dir` if the pack is invalid.
+pub fn find_language_pack(
+    code: &str,
+    lang_dirs: &[PathBuf],
+) -> Result<LanguagePack, Box<dyn std::error::Error>> {
+    for lang_dir in lang_dirs {
+        let pack_path = lang_dir.join(code);
+        if pack_path.join(PACK_FILE).is_file() {
+            return LanguagePack::load_dir(&pack_path);
+        }
+    }
+
+    #[cfg(feature = "builtin-dictionary")]
+    if let Some(dictionary) = super::builtin::BUILTIN_DICTIONARIES
+        .iter()
+        .find(|dictionary| dictionary.name == code)
+    {
+        return Ok(dictionary.language_pack());
+    }
+
+    if code == LanguagePack::default().code {
+        return Ok(LanguagePack::default());
+    }
+    Err(Box::new(Error::UnknownLanguage(
+        String::from(code),
+        available_language_packs(lang_dirs),
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn test_load_dir() {
+        let lang_dir = tempfile::tempdir().unwrap();
+        let pack_path = lang_dir.path().join("xx");
+        std::fs::create_dir(&pack_path).unwrap();
+        write!(
+            std::fs::File::create(pack_path.join(PACK_FILE)).unwrap(),
+            "name = Test\nalphabet = ABCDEFGHIJKLMNÑOPQRSTUVWXYZ\nkeyboard = qwertyuiop asdfghjklñ zxcvbnm"
+        )
+        .unwrap();
+        write!(
+            std::fs::File::create(pack_path.join(ANSWERS_FILE)).unwrap(),
+            "niño\nlobos"
+        )
+        .unwrap();
+        write!(
+            std::fs::File::create(pack_path.join(MESSAGES_FILE)).unwrap(),
+            "# Comment\ngame.prompt = ¿Palabra?"
+        )
+        .unwrap();
+
+        // Test case 1: Pack in a user directory
+        let lang_dirs = vec![lang_dir.path().to_path_buf()];
+        let pack = find_language_pack("xx", &lang_dirs).unwrap();
+        assert_eq!(pack.name, "Test");
+        assert_eq!(pack.keyboard[1].last(), Some(&'Ñ'));
+        assert!(pack.answers.get(&4).unwrap().contains_key("NIÑO"));
+        assert!(pack.guesses.is_empty());
+        assert_eq!(pack.messages.get("game.prompt").unwrap(), "¿Palabra?");
+        assert_eq!(find_messages("xx", &lang_dirs).unwrap(), pack.messages);
+        assert!(available_language_packs(&lang_dirs).contains(&String::from("xx")));
+
+        // Test case 2: Word outside of the alphabet
+        write!(
+            std::fs::File::create(pack_path.join(GUESSES_FILE)).unwrap(),
+            "crème"
+        )
+        .unwrap();
+        let error = find_language_pack("xx", &lang_dirs).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Word CRÈME contains characters outside of the xx alphabet."
+        );
+
+        // Test case 3: Unknown pack
+        let error = find_language_pack("zz", &lang_dirs).unwrap_err();
+        assert!(error.to_string().starts_with("No language pack zz"));
+    }
+}
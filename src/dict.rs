@@ -0,0 +1,260 @@
+use crate::error::AppError;
+use regex::RegexBuilder;
+use std::fs::File;
+use std::io::{self, BufWriter};
+use wordlers::data_loader::build::{
+    build_word_list, load_normalized_words_file, normalize_word, write_word_list, BuildOptions,
+};
+use wordlers::data_loader::compiled::write_compiled;
+use wordlers::data_loader::{
+    inspect_words_file, letter_frequencies_by_position, load_word_source, load_words_file_lenient,
+    Error,
+};
+use wordlers::messages::{message, MessageId};
+use wordlers::word_source::WordSource;
+
+/// Output path standing for the standard output.
+pub const STDOUT_PATH: &str = "-";
+
+const MAX_DISPLAYED_ENTRIES: usize = 10;
+const N_FREQUENT_LETTERS: usize = 5;
+
+/// Prints a summary of the lines skipped while loading a words file, for a game or a `dict` command.
+pub fn print_skipped_lines(skipped_lines: &[Error]) {
+    if skipped_lines.is_empty() {
+        return;
+    }
+    eprintln!(
+        "{}",
+        message(
+            MessageId::GameSkippedLines,
+            &[("count", &skipped_lines.len())]
+        )
+    );
+    for err in skipped_lines.iter().take(MAX_DISPLAYED_ENTRIES) {
+        eprintln!("  {err}");
+    }
+    if skipped_lines.len() > MAX_DISPLAYED_ENTRIES {
+        eprintln!("  ...");
+    }
+}
+
+/// Prints the statistics of a words file: word counts per length,
+/// most frequent letters by position, duplicate and invalid entries.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read or decompressed.
+pub fn dict_info(path: &str) -> Result<(), AppError> {
+    let (all_words, report) =
+        inspect_words_file(path).map_err(|err| AppError::from_file(path, err))?;
+    let mut lengths: Vec<&usize> = all_words.keys().collect();
+    lengths.sort();
+
+    println!("{}", message(MessageId::DictCountsHeader, &[]));
+    for length in &lengths {
+        println!(
+            "  {}",
+            message(
+                MessageId::DictCount,
+                &[("length", length), ("count", &all_words[length].len())]
+            )
+        );
+    }
+    let total: usize = all_words.values().map(|words| words.len()).sum();
+    println!("{}", message(MessageId::DictTotal, &[("count", &total)]));
+
+    for length in &lengths {
+        println!(
+            "{}",
+            message(MessageId::DictPositionsHeader, &[("length", length)])
+        );
+        for (i, frequencies) in letter_frequencies_by_position(&all_words[length])
+            .iter()
+            .enumerate()
+        {
+            let mut frequencies: Vec<(&char, &usize)> = frequencies.iter().collect();
+            frequencies.sort_by(|(char_a, count_a), (char_b, count_b)| {
+                count_b.cmp(count_a).then_with(|| char_a.cmp(char_b))
+            });
+            let letters = frequencies
+                .iter()
+                .take(N_FREQUENT_LETTERS)
+                .map(|(character, count)| format!("{character} {count}"))
+                .collect::<Vec<String>>()
+                .join(", ");
+            println!(
+                "  {}",
+                message(
+                    MessageId::DictPosition,
+                    &[("position", &(i + 1)), ("letters", &letters)]
+                )
+            );
+        }
+    }
+
+    if !report.duplicate_lines.is_empty() {
+        println!(
+            "{}",
+            message(
+                MessageId::DictDuplicates,
+                &[("count", &report.duplicate_lines.len())]
+            )
+        );
+        for (line, word) in report.duplicate_lines.iter().take(MAX_DISPLAYED_ENTRIES) {
+            println!(
+                "  {}",
+                message(MessageId::DictDuplicate, &[("line", line), ("word", word)])
+            );
+        }
+        if report.duplicate_lines.len() > MAX_DISPLAYED_ENTRIES {
+            println!("  ...");
+        }
+    }
+
+    if !report.invalid_lines.is_empty() {
+        println!(
+            "{}",
+            message(
+                MessageId::DictInvalid,
+                &[("count", &report.invalid_lines.len())]
+            )
+        );
+        for err in report.invalid_lines.iter().take(MAX_DISPLAYED_ENTRIES) {
+            println!("  {err}");
+        }
+        if report.invalid_lines.len() > MAX_DISPLAYED_ENTRIES {
+            println!("  ...");
+        }
+    }
+    Ok(())
+}
+
+/// Checks whether `word` is listed in a words file, and prints its length and frequency if so.
+/// Invalid lines of the file are ignored, and a compiled dictionary is looked up in place.
+///
+/// # Returns
+///
+/// Whether the word is in the file.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read or decompressed.
+pub fn dict_check(path: &str, word: &str) -> Result<bool, AppError> {
+    let (all_words, _) =
+        load_word_source(path, true).map_err(|err| AppError::from_file(path, err))?;
+    let word = word.trim().to_uppercase();
+    let length = word.chars().count();
+
+    match all_words.frequency(&word) {
+        Some(frequency) => {
+            println!(
+                "{}",
+                message(
+                    MessageId::DictPresent,
+                    &[
+                        ("word", &word),
+                        ("path", &path),
+                        ("length", &length),
+                        ("frequency", &frequency)
+                    ]
+                )
+            );
+            Ok(true)
+        }
+        None => {
+            println!(
+                "{}",
+                message(MessageId::DictAbsent, &[("word", &word), ("path", &path)])
+            );
+            Ok(false)
+        }
+    }
+}
+
+/// Merges words files into a sorted, deduplicated list written to `output`, or to the standard output if it is `STDOUT_PATH`.
+///
+/// # Arguments
+///
+/// * `inputs` - The paths of the words files to merge. Invalid lines are skipped and summarised.
+/// * `output` - The path of the file to write.
+/// * `blocklists` - The paths of words files listing words to drop, e.g. profanity.
+/// * `exclude_patterns` - Regular expressions, matched case-insensitively; matching words are dropped.
+/// * `options` - The other filters, completed with the `blocklists` and `exclude_patterns`.
+///
+/// # Errors
+///
+/// Returns an error if a file cannot be read or written, or a regular expression is invalid.
+pub fn dict_build(
+    inputs: &[String],
+    output: &str,
+    blocklists: &[String],
+    exclude_patterns: &[String],
+    mut options: BuildOptions,
+) -> Result<(), AppError> {
+    for pattern in exclude_patterns {
+        options
+            .exclude_patterns
+            .push(RegexBuilder::new(pattern).case_insensitive(true).build()?);
+    }
+    for path in blocklists {
+        let (blocked_words, skipped_lines) =
+            load_normalized_words_file(path).map_err(|err| AppError::from_file(path, err))?;
+        print_skipped_lines(&skipped_lines);
+        options.blocklist.extend(
+            blocked_words
+                .values()
+                .flat_map(|words| words.keys())
+                .map(|word| normalize_word(word, options.strip_accents)),
+        );
+    }
+
+    let mut all_words = Vec::new();
+    for path in inputs {
+        let (words, skipped_lines) =
+            load_normalized_words_file(path).map_err(|err| AppError::from_file(path, err))?;
+        print_skipped_lines(&skipped_lines);
+        all_words.push(words);
+    }
+    let words = build_word_list(&all_words, &options);
+
+    if output == STDOUT_PATH {
+        write_word_list(io::stdout().lock(), &words)?;
+    } else {
+        File::create(output)
+            .and_then(|file| write_word_list(BufWriter::new(file), &words))
+            .map_err(|err| AppError::File(String::from(output), err))?;
+    }
+    eprintln!(
+        "{}",
+        message(
+            MessageId::DictBuilt,
+            &[("count", &words.len()), ("path", &output)]
+        )
+    );
+    Ok(())
+}
+
+/// Converts a words file into a compiled dictionary, loaded faster than a text list.
+/// Invalid lines of the input are skipped and summarised.
+///
+/// # Errors
+///
+/// Returns an error if a file cannot be read or written, or the words cannot be compiled.
+pub fn dict_compile(input: &str, output: &str) -> Result<(), AppError> {
+    let (all_words, skipped_lines) =
+        load_words_file_lenient(input).map_err(|err| AppError::from_file(input, err))?;
+    print_skipped_lines(&skipped_lines);
+    let file = File::create(output).map_err(|err| AppError::File(String::from(output), err))?;
+    write_compiled(BufWriter::new(file), &all_words)
+        .map_err(|err| AppError::from_file(output, err))?;
+    let total: usize = all_words.values().map(|words| words.len()).sum();
+    eprintln!(
+        "{}",
+        message(
+            MessageId::DictCompiled,
+            &[("count", &total), ("path", &output)]
+        )
+    );
+    Ok(())
+}
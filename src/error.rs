@@ -0,0 +1,225 @@
+use crate::cli::GuessIterationError;
+use core::fmt;
+use std::error::Error;
+use std::io;
+use wordlers::data_loader::{self, language_pack};
+use wordlers::messages::{message, MessageId};
+use wordlers::stats;
+use wordlers::transcript;
+
+/// Exit status when `dict check` does not find the word.
+pub const EXIT_NOT_FOUND: u8 = 1;
+/// Exit status on invalid command-line arguments, as used by `clap`.
+pub const EXIT_USAGE: u8 = 2;
+/// Exit status when the game is abandoned, by closing the input or pressing Ctrl-C.
+pub const EXIT_ABANDONED: u8 = 3;
+/// Exit status when a word list, dictionary, language pack, stats file or transcript is invalid,
+/// or holds no suitable word.
+pub const EXIT_DATA: u8 = 4;
+/// Exit status when a file cannot be read or written, or a server cannot be reached.
+pub const EXIT_IO: u8 = 5;
+/// Exit status when the user input cannot be read.
+pub const EXIT_INPUT: u8 = 6;
+/// Exit status on an unexpected error, as `EX_SOFTWARE` of `sysexits.h`.
+pub const EXIT_UNEXPECTED: u8 = 70;
+
+/// Represents every error that can stop the program.
+#[derive(Debug)]
+pub enum AppError {
+    /// No word of the requested length exists. (requested length, available lengths)
+    NoWordThisLength(usize, Vec<usize>),
+    /// A word list or dictionary is invalid.
+    Data(data_loader::Error),
+    /// A language pack is invalid or unknown.
+    LanguagePack(language_pack::Error),
+    /// The stats file cannot be read, written or understood.
+    Stats(stats::Error),
+    /// A transcript cannot be read or understood, or does not match the rules.
+    Transcript(transcript::Error),
+    /// A file cannot be read or written. (path, error)
+    File(String, io::Error),
+    /// The server cannot be reached. (address, error)
+    Connection(String, io::Error),
+    /// Another I/O operation failed.
+    Io(io::Error),
+    /// The user input cannot be read.
+    Input(GuessIterationError),
+    /// A command-line argument is invalid, e.g. a malformed regular expression.
+    Usage(Box<dyn Error>),
+    /// Any other error.
+    Other(Box<dyn Error>),
+}
+
+impl AppError {
+    /// Converts an error raised while accessing the file at `path`, so that I/O errors name the file.
+    pub fn from_file(path: &str, err: Box<dyn Error>) -> Self {
+        match err.downcast::<io::Error>() {
+            Ok(err) => AppError::File(String::from(path), *err),
+            Err(err) => AppError::from(err),
+        }
+    }
+
+    /// Returns the exit status documented for this class of error.
+    #[must_use]
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            AppError::NoWordThisLength(..)
+            | AppError::Data(_)
+            | AppError::LanguagePack(_)
+            | AppError::Stats(stats::Error::Malformed(..))
+            | AppError::Transcript(
+                transcript::Error::Malformed(..)
+                | transcript::Error::MissingRound(..)
+                | transcript::Error::Tampered(_),
+            ) => EXIT_DATA,
+            AppError::File(..)
+            | AppError::Connection(..)
+            | AppError::Io(_)
+            | AppError::Stats(stats::Error::Io(..))
+            | AppError::Transcript(transcript::Error::Io(..)) => EXIT_IO,
+            AppError::Input(_) => EXIT_INPUT,
+            AppError::Usage(_) => EXIT_USAGE,
+            AppError::Other(_) => EXIT_UNEXPECTED,
+        }
+    }
+}
+
+impl Error for AppError {}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AppError::NoWordThisLength(length, available_lengths) => {
+                let lengths = available_lengths
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<String>>()
+                    .join(", ");
+                write!(
+                    f,
+                    "{}",
+                    message(
+                        MessageId::ErrorNoWordThisLength,
+                        &[("length", length), ("lengths", &lengths)]
+                    )
+                )
+            }
+            AppError::Data(err) => write!(f, "{err}"),
+            AppError::LanguagePack(err) => write!(f, "{err}"),
+            AppError::Stats(err) => write!(f, "{err}"),
+            AppError::Transcript(err) => write!(f, "{err}"),
+            AppError::File(path, err) => write!(
+                f,
+                "{}",
+                message(MessageId::ErrorFile, &[("path", path), ("error", err)])
+            ),
+            AppError::Connection(address, err) => write!(
+                f,
+                "{}",
+                message(
+                    MessageId::ErrorConnection,
+                    &[("address", address), ("error", err)]
+                )
+            ),
+            AppError::Io(err) => write!(f, "{}", message(MessageId::ErrorIo, &[("error", err)])),
+            AppError::Input(err) => write!(f, "{err}"),
+            AppError::Usage(err) | AppError::Other(err) => write!(f, "{err}"),
+        }
+    }
+}
+
+impl From<data_loader::Error> for AppError {
+    fn from(err: data_loader::Error) -> Self {
+        AppError::Data(err)
+    }
+}
+
+impl From<language_pack::Error> for AppError {
+    fn from(err: language_pack::Error) -> Self {
+        AppError::LanguagePack(err)
+    }
+}
+
+impl From<stats::Error> for AppError {
+    fn from(err: stats::Error) -> Self {
+        AppError::Stats(err)
+    }
+}
+
+impl From<transcript::Error> for AppError {
+    fn from(err: transcript::Error) -> Self {
+        AppError::Transcript(err)
+    }
+}
+
+impl From<io::Error> for AppError {
+    fn from(err: io::Error) -> Self {
+        AppError::Io(err)
+    }
+}
+
+impl From<GuessIterationError> for AppError {
+    fn from(err: GuessIterationError) -> Self {
+        AppError::Input(err)
+    }
+}
+
+impl From<regex::Error> for AppError {
+    fn from(err: regex::Error) -> Self {
+        AppError::Usage(Box::new(err))
+    }
+}
+
+impl From<Box<dyn Error>> for AppError {
+    fn from(err: Box<dyn Error>) -> Self {
+        let err = match err.downcast::<data_loader::Error>() {
+            Ok(err) => return AppError::Data(*err),
+            Err(err) => err,
+        };
+        let err = match err.downcast::<language_pack::Error>() {
+            Ok(err) => return AppError::LanguagePack(*err),
+            Err(err) => err,
+        };
+        let err = match err.downcast::<io::Error>() {
+            Ok(err) => return AppError::Io(*err),
+            Err(err) => err,
+        };
+        let err = match err.downcast::<GuessIterationError>() {
+            Ok(err) => return AppError::Input(*err),
+            Err(err) => err,
+        };
+        match err.downcast::<regex::Error>() {
+            Ok(err) => AppError::Usage(err),
+            Err(err) => AppError::Other(err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_app_error() {
+        // Test case 1: Boxed errors are sorted into their class
+        let err = AppError::from(Box::new(data_loader::Error::EmptyWordVec(5)) as Box<dyn Error>);
+        assert_eq!(err.exit_code(), EXIT_DATA);
+        let err = AppError::from_file(
+            "words.txt",
+            Box::new(io::Error::from(io::ErrorKind::NotFound)),
+        );
+        assert!(matches!(err, AppError::File(ref path, _) if path == "words.txt"));
+        assert_eq!(err.exit_code(), EXIT_IO);
+
+        let err = AppError::from(Box::<dyn Error>::from("unexpected"));
+        assert!(matches!(err, AppError::Other(_)));
+        assert_eq!(err.exit_code(), EXIT_UNEXPECTED);
+
+        // Test case 2: Available lengths are listed
+        let err = AppError::NoWordThisLength(7, vec![4, 5, 6]);
+        assert_eq!(
+            err.to_string(),
+            "No word of length 7 found. Available lengths: 4, 5, 6."
+        );
+    }
+}
@@ -0,0 +1,708 @@
+use crate::game_logic::{analyze_guess, CharacterState};
+use crate::solver::FeedbackRow;
+use crate::timer::{Clock, SystemClock};
+use crate::user_input::format_and_check::{input_string_in_alphabet, UserInputError};
+use crate::word_source::WordSource;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::time::{Duration, Instant};
+
+/// How a round ended, serialized as `won`, `lost` or `abandoned`.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Outcome {
+    /// The word was guessed.
+    Won,
+    /// Every attempt was used.
+    Lost,
+    /// The player stopped playing before the end, e.g. by closing the input or pressing Ctrl-C.
+    Abandoned,
+}
+
+/// Something that happened during a round, sent to every `Observer` of the round.
+#[derive(Debug)]
+pub enum GameEvent {
+    /// The round started. (word length, maximum number of attempts)
+    RoundStarted(usize, usize),
+    /// A guess was not valid, and did not use an attempt. (input, reason)
+    GuessRejected(String, UserInputError),
+    /// A guess was scored. (uppercased guess, feedback for each character, time taken by the guess)
+    GuessScored(String, Vec<CharacterState>, Duration),
+    /// A character of the word was revealed. (position, character)
+    HintUsed(usize, char),
+    /// No guess was made in the time allowed, and the attempt was lost. (time taken by the attempt)
+    GuessTimedOut(Duration),
+    /// The round ended. (outcome, word to guess, total time taken by the guesses)
+    RoundFinished(Outcome, String, Duration),
+}
+
+/// Receives the events of the rounds it is registered to, e.g. to display them, log them or collect statistics.
+pub trait Observer {
+    /// Handles an `event` of the round.
+    fn notify(&mut self, event: &GameEvent);
+}
+
+/// A round of the game: a word to guess in a limited number of attempts.
+///
+/// The round checks and scores the guesses it is given and notifies its observers of what happens.
+/// Reading the guesses is left to the caller.
+///
+/// In hard mode, a guess must fit the feedback and hints received so far, as if it could be the word.
+///
+/// The round times each guess, from the start of the round or the previous guess,
+/// on the system clock unless another is given with `Round::with_clock`.
+/// The clock can be restarted with `Round::resume`, so that only the time spent by the player counts.
+///
+/// # Examples
+///
+/// ```
+/// use wordlers::game::{GameEvent, Observer, Outcome, Round};
+///
+/// #[derive(Default)]
+/// struct Counter(usize);
+///
+/// impl Observer for Counter {
+///     fn notify(&mut self, event: &GameEvent) {
+///         if let GameEvent::GuessScored(..) = event {
+///             self.0 += 1;
+///         }
+///     }
+/// }
+///
+/// let alphabet: Vec<char> = ('A'..='Z').collect();
+/// let mut counter = Counter::default();
+/// let mut round = Round::new("CRANE", &alphabet, 6);
+/// round.add_observer(&mut counter);
+/// round.start();
+/// round.guess("crate");
+/// round.guess("cr4ne");
+/// round.guess("crane");
+/// assert_eq!(round.outcome(), Some(Outcome::Won));
+/// drop(round);
+/// assert_eq!(counter.0, 2);
+/// ```
+pub struct Round<'a> {
+    word: String,
+    alphabet: Vec<char>,
+    max_attempts: usize,
+    hard_mode: bool,
+    dictionaries: Vec<&'a dyn WordSource>,
+    rows: Vec<FeedbackRow>,
+    hints: Vec<(usize, char)>,
+    timeouts: usize,
+    guess_times: Vec<Duration>,
+    clock: &'a dyn Clock,
+    turn_started: Option<Instant>,
+    outcome: Option<Outcome>,
+    observers: Vec<&'a mut dyn Observer>,
+}
+
+impl<'a> Round<'a> {
+    /// Creates a round to guess the uppercase `word` in at most `max_attempts`,
+    /// guesses being made of the characters of the `alphabet`.
+    #[must_use]
+    pub fn new(word: &str, alphabet: &[char], max_attempts: usize) -> Self {
+        Round {
+            word: String::from(word),
+            alphabet: alphabet.to_vec(),
+            max_attempts,
+            hard_mode: false,
+            dictionaries: Vec::new(),
+            rows: Vec::new(),
+            hints: Vec::new(),
+            timeouts: 0,
+            guess_times: Vec::new(),
+            clock: &SystemClock,
+            turn_started: None,
+            outcome: None,
+            observers: Vec::new(),
+        }
+    }
+
+    /// Plays the round in hard mode if `hard_mode` is set: a guess must fit the feedback and hints so far.
+    #[must_use]
+    pub fn with_hard_mode(mut self, hard_mode: bool) -> Self {
+        self.hard_mode = hard_mode;
+        self
+    }
+
+    /// Only accepts guesses found in one of the dictionaries, `words` being added to them, or the word itself.
+    /// Without a dictionary, any word of the alphabet is accepted.
+    #[must_use]
+    pub fn with_dictionary(mut self, words: &'a dyn WordSource) -> Self {
+        self.dictionaries.push(words);
+        self
+    }
+
+    /// Times the guesses on the `clock` instead of the system clock.
+    #[must_use]
+    pub fn with_clock(mut self, clock: &'a dyn Clock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Registers an `observer`, notified of every following event of the round.
+    pub fn add_observer(&mut self, observer: &'a mut dyn Observer) {
+        self.observers.push(observer);
+    }
+
+    fn emit(&mut self, event: &GameEvent) {
+        for observer in &mut self.observers {
+            observer.notify(event);
+        }
+    }
+
+    /// Returns the word to guess.
+    #[must_use]
+    pub fn word(&self) -> &str {
+        &self.word
+    }
+
+    /// Returns the length of the word to guess, in characters.
+    #[must_use]
+    pub fn word_length(&self) -> usize {
+        self.word.chars().count()
+    }
+
+    /// Returns the maximum number of attempts.
+    #[must_use]
+    pub fn max_attempts(&self) -> usize {
+        self.max_attempts
+    }
+
+    /// Returns the number of attempts left.
+    #[must_use]
+    pub fn remaining_attempts(&self) -> usize {
+        self.max_attempts
+            .saturating_sub(self.rows.len() + self.timeouts)
+    }
+
+    /// Returns the scored guesses so far.
+    #[must_use]
+    pub fn rows(&self) -> &[FeedbackRow] {
+        &self.rows
+    }
+
+    /// Returns the characters revealed by hints so far, with their position.
+    #[must_use]
+    pub fn hints(&self) -> &[(usize, char)] {
+        &self.hints
+    }
+
+    /// Returns the time taken by each attempt, scored or timed out.
+    #[must_use]
+    pub fn guess_times(&self) -> &[Duration] {
+        &self.guess_times
+    }
+
+    /// Returns the total time taken by the attempts.
+    #[must_use]
+    pub fn elapsed(&self) -> Duration {
+        self.guess_times.iter().sum()
+    }
+
+    /// Returns how the round ended, or `None` if it is still going on.
+    #[must_use]
+    pub fn outcome(&self) -> Option<Outcome> {
+        self.outcome
+    }
+
+    /// Checks whether the round ended.
+    #[must_use]
+    pub fn is_finished(&self) -> bool {
+        self.outcome.is_some()
+    }
+
+    /// Starts the round and its clock, notifying the observers.
+    pub fn start(&mut self) {
+        self.turn_started = Some(self.clock.now());
+        self.emit(&GameEvent::RoundStarted(
+            self.word_length(),
+            self.max_attempts,
+        ));
+    }
+
+    /// Restarts the clock of the round, e.g. when the turn of its player comes back after another player's,
+    /// so that the next guess is timed from now.
+    pub fn resume(&mut self) {
+        self.turn_started = Some(self.clock.now());
+    }
+
+    /// Returns the time taken by the current attempt, restarting the clock for the next one.
+    fn time_attempt(&mut self) -> Duration {
+        let now = self.clock.now();
+        let time = self
+            .turn_started
+            .map_or(Duration::ZERO, |turn_started| now - turn_started);
+        self.turn_started = Some(now);
+        self.guess_times.push(time);
+        time
+    }
+
+    /// Ends the round with the given `outcome`.
+    fn finish(&mut self, outcome: Outcome) {
+        self.outcome = Some(outcome);
+        self.emit(&GameEvent::RoundFinished(
+            outcome,
+            self.word.clone(),
+            self.elapsed(),
+        ));
+    }
+
+    /// Checks that the `input` is a valid guess: a word of the right length, made of the characters of the alphabet,
+    /// found in the dictionaries if there are any, and in hard mode, fitting the feedback and hints so far.
+    ///
+    /// # Returns
+    ///
+    /// The uppercased guess.
+    ///
+    /// # Errors
+    ///
+    /// Returns the reason why the guess would be rejected.
+    pub fn check(&self, input: &str) -> Result<String, UserInputError> {
+        let guess = input_string_in_alphabet(input, self.word_length(), &self.alphabet)?;
+        if !self.dictionaries.is_empty()
+            && guess != self.word
+            && !self.dictionaries.iter().any(|words| words.contains(&guess))
+        {
+            return Err(UserInputError::NotInDictionary(guess));
+        }
+        if self.hard_mode && !self.fits_knowledge(&guess) {
+            return Err(UserInputError::HardMode(guess));
+        }
+        Ok(guess)
+    }
+
+    /// Checks whether the word could be `guess`, given the feedback and hints so far.
+    fn fits_knowledge(&self, guess: &str) -> bool {
+        self.rows
+            .iter()
+            .all(|(row_guess, feedback)| analyze_guess(guess, row_guess) == *feedback)
+            && self
+                .hints
+                .iter()
+                .all(|(position, character)| guess.chars().nth(*position) == Some(*character))
+    }
+
+    /// Checks and scores a guess. The round ends once the word is found or every attempt is used.
+    ///
+    /// # Returns
+    ///
+    /// The feedback for each character of the guess,
+    /// or `None` if the guess was rejected or the round is already finished.
+    pub fn guess(&mut self, input: &str) -> Option<Vec<CharacterState>> {
+        if self.is_finished() {
+            return None;
+        }
+        let guess = match self.check(input) {
+            Ok(guess) => guess,
+            Err(err) => {
+                self.emit(&GameEvent::GuessRejected(String::from(input.trim()), err));
+                return None;
+            }
+        };
+
+        let time = self.time_attempt();
+        let feedback = analyze_guess(&self.word, &guess);
+        self.rows.push((guess.clone(), feedback.clone()));
+        self.emit(&GameEvent::GuessScored(guess, feedback.clone(), time));
+
+        if feedback
+            .iter()
+            .all(|state| matches!(state, CharacterState::Good(_)))
+        {
+            self.finish(Outcome::Won);
+        } else if self.remaining_attempts() == 0 {
+            self.finish(Outcome::Lost);
+        }
+        Some(feedback)
+    }
+
+    /// Uses up the current attempt without a guess, because the player ran out of time.
+    /// The round is lost if it was the last attempt.
+    pub fn time_out(&mut self) {
+        if self.is_finished() {
+            return;
+        }
+        let time = self.time_attempt();
+        self.timeouts += 1;
+        self.emit(&GameEvent::GuessTimedOut(time));
+        if self.remaining_attempts() == 0 {
+            self.finish(Outcome::Lost);
+        }
+    }
+
+    /// Reveals the first character of the word that was neither found nor already revealed.
+    ///
+    /// # Returns
+    ///
+    /// The position and the character revealed,
+    /// or `None` if there is nothing left to reveal or the round is already finished.
+    pub fn hint(&mut self) -> Option<(usize, char)> {
+        if self.is_finished() {
+            return None;
+        }
+        let (position, character) = self.word.chars().enumerate().find(|(i, _)| {
+            !self.hints.iter().any(|(position, _)| position == i)
+                && !self
+                    .rows
+                    .iter()
+                    .any(|(_, feedback)| matches!(feedback[*i], CharacterState::Good(_)))
+        })?;
+        self.hints.push((position, character));
+        self.emit(&GameEvent::HintUsed(position, character));
+        Some((position, character))
+    }
+
+    /// Ends the round as abandoned, e.g. when the player stops answering.
+    pub fn abandon(&mut self) {
+        if !self.is_finished() {
+            self.finish(Outcome::Abandoned);
+        }
+    }
+
+    /// Returns the result of the round, scored as by `RoundResult::score`, or `None` if it is still going on.
+    #[must_use]
+    pub fn result(&self) -> Option<RoundResult> {
+        let mut result = RoundResult {
+            outcome: self.outcome?,
+            word_length: self.word_length(),
+            attempts: self.rows.len(),
+            unused_attempts: self.remaining_attempts(),
+            hints: self.hints.len(),
+            time: self.elapsed(),
+            hard_mode: self.hard_mode,
+            score: 0,
+        };
+        result.score = result.score();
+        Some(result)
+    }
+}
+
+/// What a round came to, to rank players beyond winning or losing.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RoundResult {
+    /// How the round ended.
+    pub outcome: Outcome,
+    /// Length of the word to guess, in characters.
+    pub word_length: usize,
+    /// Number of guesses scored.
+    pub attempts: usize,
+    /// Number of attempts left.
+    pub unused_attempts: usize,
+    /// Number of characters revealed by hints.
+    pub hints: usize,
+    /// Total time taken by the attempts.
+    pub time: Duration,
+    /// Whether the round was played in hard mode.
+    pub hard_mode: bool,
+    /// Score of the round, see `RoundResult::score`.
+    pub score: usize,
+}
+
+/// Points scored per character of a word found.
+const POINTS_PER_CHARACTER: usize = 10;
+/// Points scored per attempt left unused.
+const POINTS_PER_UNUSED_ATTEMPT: usize = 10;
+/// Points lost per hint used.
+const POINTS_PER_HINT: usize = 5;
+
+impl RoundResult {
+    /// Computes the score of the round: nothing unless the word was found, otherwise
+    /// 10 points per character of the word and per attempt left unused, minus 5 points per hint,
+    /// the whole being increased by half in hard mode.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wordlers::game::{Outcome, RoundResult};
+    /// use std::time::Duration;
+    ///
+    /// let result = RoundResult {
+    ///     outcome: Outcome::Won,
+    ///     word_length: 5,
+    ///     attempts: 3,
+    ///     unused_attempts: 2,
+    ///     hints: 1,
+    ///     time: Duration::from_secs(40),
+    ///     hard_mode: true,
+    ///     score: 0,
+    /// };
+    /// assert_eq!(result.score(), 97);
+    /// ```
+    #[must_use]
+    pub fn score(&self) -> usize {
+        if self.outcome != Outcome::Won {
+            return 0;
+        }
+        let score = (POINTS_PER_CHARACTER * self.word_length
+            + POINTS_PER_UNUSED_ATTEMPT * self.unused_attempts)
+            .saturating_sub(POINTS_PER_HINT * self.hints);
+        if self.hard_mode {
+            score + score / 2
+        } else {
+            score
+        }
+    }
+}
+
+/// How a player did in a race, where several players guess the same word.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RaceStanding {
+    /// Name of the player.
+    pub player: String,
+    /// How the round of the player ended.
+    pub outcome: Outcome,
+    /// Number of guesses scored.
+    pub attempts: usize,
+    /// Total time taken by the guesses.
+    pub time: Duration,
+}
+
+impl RaceStanding {
+    /// Returns the time compared between players, to the tenth of a second, as it is displayed.
+    fn compared_time(&self) -> u128 {
+        self.time.as_millis() / 100
+    }
+}
+
+/// Ranks the `standings` of a race: the players who found the word come first, the fastest first,
+/// ties being broken by fewer guesses. The other players keep their order.
+///
+/// # Returns
+///
+/// The winner of the race, or `None` if nobody found the word.
+pub fn rank_race(standings: &mut [RaceStanding]) -> Option<&RaceStanding> {
+    standings.sort_by(|a, b| match (a.outcome, b.outcome) {
+        (Outcome::Won, Outcome::Won) => a
+            .compared_time()
+            .cmp(&b.compared_time())
+            .then(a.attempts.cmp(&b.attempts)),
+        (Outcome::Won, _) => Ordering::Less,
+        (_, Outcome::Won) => Ordering::Greater,
+        _ => Ordering::Equal,
+    });
+    standings
+        .first()
+        .filter(|standing| standing.outcome == Outcome::Won)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::timer::ManualClock;
+    use crate::word_source::WordList;
+
+    /// Records the events it receives, as text.
+    #[derive(Default)]
+    struct Recorder(Vec<String>);
+
+    impl Observer for Recorder {
+        fn notify(&mut self, event: &GameEvent) {
+            self.0.push(match event {
+                GameEvent::RoundStarted(length, attempts) => format!("started {length} {attempts}"),
+                GameEvent::GuessRejected(input, _) => format!("rejected {input}"),
+                GameEvent::GuessScored(guess, ..) => format!("scored {guess}"),
+                GameEvent::HintUsed(position, character) => format!("hint {position} {character}"),
+                GameEvent::GuessTimedOut(time) => format!("timed out {}", time.as_secs()),
+                GameEvent::RoundFinished(outcome, word, _) => {
+                    format!("finished {outcome:?} {word}")
+                }
+            });
+        }
+    }
+
+    #[test]
+    fn test_round() {
+        let alphabet: Vec<char> = ('A'..='Z').collect();
+
+        // Test case 1: Events of a won round
+        let mut recorder = Recorder::default();
+        let mut round = Round::new("CRANE", &alphabet, 3);
+        round.add_observer(&mut recorder);
+        round.start();
+        assert!(round.guess("cran").is_none());
+        assert!(round.guess("CRATE").is_some());
+        assert_eq!(round.hint(), Some((3, 'N')));
+        assert!(round.guess("crane").is_some());
+        assert!(round.guess("crane").is_none());
+        assert_eq!(round.outcome(), Some(Outcome::Won));
+        drop(round);
+        assert_eq!(
+            recorder.0,
+            vec![
+                "started 5 3",
+                "rejected cran",
+                "scored CRATE",
+                "hint 3 N",
+                "scored CRANE",
+                "finished Won CRANE",
+            ]
+        );
+
+        // Test case 2: Every attempt used, and timed
+        let mut round = Round::new("CRANE", &alphabet, 2);
+        round.start();
+        round.guess("CRATE");
+        assert!(!round.is_finished());
+        assert_eq!(round.remaining_attempts(), 1);
+        round.guess("TRACE");
+        assert_eq!(round.outcome(), Some(Outcome::Lost));
+        assert_eq!(round.guess_times().len(), 2);
+        assert_eq!(round.elapsed(), round.guess_times().iter().sum());
+
+        // Test case 3: Attempts timed on another clock, and lost to time outs
+        let clock = ManualClock::new();
+        let mut recorder = Recorder::default();
+        let mut round = Round::new("CRANE", &alphabet, 2).with_clock(&clock);
+        round.add_observer(&mut recorder);
+        round.start();
+        clock.advance(Duration::from_secs(4));
+        round.guess("CRATE");
+        clock.advance(Duration::from_secs(10));
+        round.time_out();
+        assert_eq!(round.outcome(), Some(Outcome::Lost));
+        assert_eq!(
+            round.guess_times(),
+            [Duration::from_secs(4), Duration::from_secs(10)]
+        );
+        assert_eq!(round.elapsed(), Duration::from_secs(14));
+        drop(round);
+        assert_eq!(
+            recorder.0,
+            vec![
+                "started 5 2",
+                "scored CRATE",
+                "timed out 10",
+                "finished Lost CRANE"
+            ]
+        );
+
+        // Test case 4: Hints run out once every character is known
+        let mut round = Round::new("AB", &alphabet, 2);
+        round.guess("AC");
+        assert_eq!(round.hint(), Some((1, 'B')));
+        assert_eq!(round.hint(), None);
+
+        // Test case 5: An abandoned round accepts no more guesses
+        round.abandon();
+        assert_eq!(round.outcome(), Some(Outcome::Abandoned));
+        assert!(round.guess("AB").is_none());
+    }
+
+    #[test]
+    fn test_hard_mode() {
+        let alphabet: Vec<char> = ('A'..='Z').collect();
+        let mut round = Round::new("CRANE", &alphabet, 6).with_hard_mode(true);
+        round.start();
+
+        // Test case 1: Guesses must keep the characters found
+        round.guess("TRACE");
+        assert!(matches!(
+            round.check("HOUSE"),
+            Err(UserInputError::HardMode(ref guess)) if guess == "HOUSE"
+        ));
+        assert!(round.check("CRATE").is_err());
+        assert!(round.check("CRAVE").is_ok());
+
+        // Test case 2: Guesses must use the hints
+        let mut round = Round::new("CRANE", &alphabet, 6).with_hard_mode(true);
+        round.start();
+        assert_eq!(round.hint(), Some((0, 'C')));
+        assert!(round.check("TRACE").is_err());
+        assert!(round.check("CRATE").is_ok());
+    }
+
+    #[test]
+    fn test_dictionary() {
+        let alphabet: Vec<char> = ('A'..='Z').collect();
+        let answers = WordList::from(["CRANE"]);
+        let guesses = WordList::from(["TRACE"]);
+
+        // Test case 1: Without a dictionary, any word of the alphabet is accepted
+        let round = Round::new("CRANE", &alphabet, 6);
+        assert!(round.check("QWXYZ").is_ok());
+
+        // Test case 2: Guesses must be in one of the dictionaries
+        let round = Round::new("CRANE", &alphabet, 6)
+            .with_dictionary(&answers)
+            .with_dictionary(&guesses);
+        assert!(round.check("crane").is_ok());
+        assert!(round.check("trace").is_ok());
+        assert!(matches!(
+            round.check("qwxyz"),
+            Err(UserInputError::NotInDictionary(ref guess)) if guess == "QWXYZ"
+        ));
+
+        // Test case 3: The word itself is accepted even if no dictionary has it
+        let round = Round::new("SLATE", &alphabet, 6).with_dictionary(&guesses);
+        assert!(round.check("SLATE").is_ok());
+    }
+
+    #[test]
+    fn test_result() {
+        let alphabet: Vec<char> = ('A'..='Z').collect();
+
+        // Test case 1: A won round scores its word length and unused attempts, less its hints
+        let mut round = Round::new("CRANE", &alphabet, 6);
+        round.guess("TRACE");
+        round.hint();
+        round.guess("CRANE");
+        let result = round.result().unwrap();
+        assert_eq!(result.outcome, Outcome::Won);
+        assert_eq!(
+            (result.attempts, result.unused_attempts, result.hints),
+            (2, 4, 1)
+        );
+        assert!(!result.hard_mode);
+        assert_eq!(result.score, 50 + 40 - 5);
+
+        // Test case 2: Hard mode scores half more
+        let mut round = Round::new("CRANE", &alphabet, 6).with_hard_mode(true);
+        round.guess("CRANE");
+        assert_eq!(round.result().unwrap().score, (50 + 50) * 3 / 2);
+
+        // Test case 3: Longer words score more
+        let mut round = Round::new("CRANES", &alphabet, 6);
+        round.guess("CRANES");
+        assert_eq!(round.result().unwrap().score, 60 + 50);
+
+        // Test case 4: Lost rounds score nothing, and rounds going on have no result yet
+        let mut round = Round::new("CRANE", &alphabet, 2);
+        round.guess("TRACE");
+        assert_eq!(round.result(), None);
+        round.guess("TRACE");
+        assert_eq!(round.result().unwrap().score, 0);
+    }
+
+    #[test]
+    fn test_rank_race() {
+        let standing = |player: &str, outcome, attempts, millis| RaceStanding {
+            player: String::from(player),
+            outcome,
+            attempts,
+            time: Duration::from_millis(millis),
+        };
+
+        // Test case 1: The fastest player who found the word wins
+        let mut standings = vec![
+            standing("alice", Outcome::Lost, 5, 1_000),
+            standing("bob", Outcome::Won, 4, 30_000),
+            standing("carol", Outcome::Won, 3, 20_000),
+        ];
+        assert_eq!(rank_race(&mut standings).unwrap().player, "carol");
+        assert_eq!(standings[1].player, "bob");
+        assert_eq!(standings[2].player, "alice");
+
+        // Test case 2: Ties are broken by fewer guesses
+        let mut standings = vec![
+            standing("alice", Outcome::Won, 4, 20_010),
+            standing("bob", Outcome::Won, 3, 20_040),
+        ];
+        assert_eq!(rank_race(&mut standings).unwrap().player, "bob");
+
+        // Test case 3: Nobody found the word
+        let mut standings = vec![standing("alice", Outcome::Abandoned, 1, 0)];
+        assert_eq!(rank_race(&mut standings), None);
+    }
+}
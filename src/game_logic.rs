@@ -1,7 +1,14 @@
+use crate::messages::{message, MessageId};
+use core::fmt;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
 use std::{collections::HashMap, hash::BuildHasher};
 
 /// Represents the state of a character in the guess word.
-#[derive(Clone, PartialEq)]
+///
+/// It is serialized as `{"state": "misplaced", "character": "A"}`, `character` being absent for `NotTried`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "state", content = "character", rename_all = "snake_case")]
 pub enum CharacterState {
     /// The character has not been tried yet.
     NotTried,
@@ -33,7 +40,7 @@ fn mark_perfect_characters<S: BuildHasher>(
     guess_string: &str,
     char_counts: &mut HashMap<char, i32, S>,
 ) -> Vec<CharacterState> {
-    let mut results = vec![CharacterState::NotTried; guess_word.len()];
+    let mut results = vec![CharacterState::NotTried; guess_word.chars().count()];
 
     for (i, (char1, char2)) in guess_word.chars().zip(guess_string.chars()).enumerate() {
         if char1 == char2 {
@@ -134,3 +141,206 @@ pub fn analyze_guess(guess_word: &str, preprocessed_try: &str) -> Vec<CharacterS
 
     mark_misplaced_characters(guess_word, preprocessed_try, char_counts, results)
 }
+
+/// Represents the feedback given for a single character, independently of the character itself.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Mark {
+    /// The character is not in the word. Encoded as `.` or `0`.
+    NotPresent,
+    /// The character is in the word but not at this place. Encoded as `Y` or `1`.
+    Misplaced,
+    /// The character is correctly placed. Encoded as `G` or `2`.
+    Good,
+}
+
+/// Represents the possible errors that can occur while parsing or applying a feedback pattern.
+#[derive(Debug, PartialEq)]
+pub enum PatternError {
+    /// The pattern contains an unknown character. (position, character)
+    UnknownCharacter(usize, char),
+    /// The pattern does not have the expected length. (expected length, given length)
+    UnexpectedLength(usize, usize),
+    /// The character states contain a `CharacterState::NotTried` character. (position)
+    NotTried(usize),
+}
+
+impl std::error::Error for PatternError {}
+
+impl fmt::Display for PatternError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            PatternError::UnknownCharacter(position, character) => message(
+                MessageId::PatternUnknownCharacter,
+                &[
+                    ("character", &format!("{character:?}")),
+                    ("position", &(position + 1)),
+                ],
+            ),
+            PatternError::UnexpectedLength(expected, given) => message(
+                MessageId::PatternUnexpectedLength,
+                &[("expected", expected), ("given", given)],
+            ),
+            PatternError::NotTried(position) => {
+                message(MessageId::PatternNotTried, &[("position", &(position + 1))])
+            }
+        };
+        write!(f, "{text}")
+    }
+}
+
+/// A textual, colourless encoding of a feedback row.
+///
+/// Each character of the row is encoded as `G` (good), `Y` (misplaced) or `.` (not present).
+/// The digit notation `2`, `1`, `0` is also accepted when parsing and is produced by the alternate format (`{:#}`).
+/// Parsing is case-insensitive.
+///
+/// # Examples
+///
+/// ```
+/// use wordlers::game_logic::{analyze_guess, FeedbackPattern};
+///
+/// let pattern: FeedbackPattern = "gy..g".parse().unwrap();
+/// assert_eq!(pattern.to_string(), "GY..G");
+/// assert_eq!(format!("{pattern:#}"), "21002");
+///
+/// let states = pattern.to_states("CRANE").unwrap();
+/// assert_eq!(FeedbackPattern::from_states(&states).unwrap(), pattern);
+/// assert_eq!(FeedbackPattern::from_states(&analyze_guess("CRATE", "CRANE")).unwrap().to_string(), "GGG.G");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FeedbackPattern(Vec<Mark>);
+
+impl FeedbackPattern {
+    /// Returns the marks of the pattern.
+    #[must_use]
+    pub fn marks(&self) -> &[Mark] {
+        &self.0
+    }
+
+    /// Builds the pattern corresponding to the result of `analyze_guess`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PatternError::NotTried` if one of the `states` is `CharacterState::NotTried`.
+    pub fn from_states(states: &[CharacterState]) -> Result<Self, PatternError> {
+        states
+            .iter()
+            .enumerate()
+            .map(|(i, state)| match state {
+                CharacterState::NotTried => Err(PatternError::NotTried(i)),
+                CharacterState::NotPresent(_) => Ok(Mark::NotPresent),
+                CharacterState::Misplaced(_) => Ok(Mark::Misplaced),
+                CharacterState::Good(_) => Ok(Mark::Good),
+            })
+            .collect::<Result<Vec<Mark>, PatternError>>()
+            .map(FeedbackPattern)
+    }
+
+    /// Applies the pattern to the characters of `guess`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `PatternError::UnexpectedLength` if `guess` does not have the length of the pattern.
+    pub fn to_states(&self, guess: &str) -> Result<Vec<CharacterState>, PatternError> {
+        let guess_length = guess.chars().count();
+        if guess_length != self.0.len() {
+            return Err(PatternError::UnexpectedLength(guess_length, self.0.len()));
+        }
+
+        Ok(guess
+            .chars()
+            .zip(&self.0)
+            .map(|(character, mark)| match mark {
+                Mark::NotPresent => CharacterState::NotPresent(character),
+                Mark::Misplaced => CharacterState::Misplaced(character),
+                Mark::Good => CharacterState::Good(character),
+            })
+            .collect())
+    }
+}
+
+impl FromStr for FeedbackPattern {
+    type Err = PatternError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.chars()
+            .enumerate()
+            .map(|(i, character)| match character.to_ascii_uppercase() {
+                'G' | '2' => Ok(Mark::Good),
+                'Y' | '1' => Ok(Mark::Misplaced),
+                '.' | '0' => Ok(Mark::NotPresent),
+                _ => Err(PatternError::UnknownCharacter(i, character)),
+            })
+            .collect::<Result<Vec<Mark>, PatternError>>()
+            .map(FeedbackPattern)
+    }
+}
+
+impl fmt::Display for FeedbackPattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for mark in &self.0 {
+            let character = match (mark, f.alternate()) {
+                (Mark::Good, false) => 'G',
+                (Mark::Misplaced, false) => 'Y',
+                (Mark::NotPresent, false) => '.',
+                (Mark::Good, true) => '2',
+                (Mark::Misplaced, true) => '1',
+                (Mark::NotPresent, true) => '0',
+            };
+            write!(f, "{character}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_feedback_pattern() {
+        // Test case 1: Both notations parse to the same pattern
+        let pattern: FeedbackPattern = "GY..G".parse().unwrap();
+        assert_eq!("21002".parse::<FeedbackPattern>().unwrap(), pattern);
+        assert_eq!(
+            pattern.to_string().parse::<FeedbackPattern>().unwrap(),
+            pattern
+        );
+        assert_eq!(
+            format!("{pattern:#}").parse::<FeedbackPattern>().unwrap(),
+            pattern
+        );
+
+        // Test case 2: Round trip through character states
+        let states = pattern.to_states("CRANE").unwrap();
+        assert_eq!(
+            states,
+            vec![
+                CharacterState::Good('C'),
+                CharacterState::Misplaced('R'),
+                CharacterState::NotPresent('A'),
+                CharacterState::NotPresent('N'),
+                CharacterState::Good('E'),
+            ]
+        );
+        assert_eq!(FeedbackPattern::from_states(&states).unwrap(), pattern);
+
+        // Test case 3: Malformed pattern
+        assert_eq!(
+            "GYX.G".parse::<FeedbackPattern>(),
+            Err(PatternError::UnknownCharacter(2, 'X'))
+        );
+
+        // Test case 4: Length mismatch
+        assert_eq!(
+            pattern.to_states("CRANES"),
+            Err(PatternError::UnexpectedLength(6, 5))
+        );
+
+        // Test case 5: Untried characters have no encoding
+        assert_eq!(
+            FeedbackPattern::from_states(&[CharacterState::Good('A'), CharacterState::NotTried]),
+            Err(PatternError::NotTried(1))
+        );
+    }
+}
@@ -1,3 +1,12 @@
 pub mod data_loader;
+pub mod game;
 pub mod game_logic;
+pub mod messages;
+pub mod protocol;
+pub mod server;
+pub mod solver;
+pub mod stats;
+pub mod timer;
+pub mod transcript;
 pub mod user_input;
+pub mod word_source;
@@ -1,39 +1,825 @@
+/// This module contains the assistant mode, helping to solve puzzles played elsewhere.
+pub mod assist;
+
 /// This module contains the command-line interface (CLI) functionality.
 pub mod cli;
 
-/// This module handles loading data.
-pub mod data_loader;
+/// This module contains the client playing in a room of a game server.
+pub mod client;
+
+/// This module contains the dictionary maintenance commands.
+pub mod dict;
+
+/// This module contains the top-level error type and the exit status of each class of error.
+pub mod error;
 
-use clap::Parser;
-use cli::game_iteration;
-use data_loader::{choose_random_word, load_words_file};
+use assist::assist_iteration;
+use clap::{Parser, Subcommand, ValueEnum};
+use cli::{
+    game_iteration, race_iteration, replay_round, survival_iteration, timed_iteration,
+    InterruptibleStdinInput, Rules,
+};
+use client::join_game;
+use dict::{dict_build, dict_check, dict_compile, dict_info, print_skipped_lines, STDOUT_PATH};
+use error::{AppError, EXIT_ABANDONED, EXIT_NOT_FOUND};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::collections::HashMap;
+use std::env;
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufReader};
+use std::net::TcpListener;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::Duration;
+use wordlers::data_loader::build::BuildOptions;
+#[cfg(feature = "builtin-dictionary")]
+use wordlers::data_loader::builtin::BUILTIN_DICTIONARIES;
+use wordlers::data_loader::language_pack::{
+    default_lang_dirs, find_language_pack, find_messages, LanguagePack,
+};
+use wordlers::data_loader::{self, choose_random_word, load_word_source, LoadedWords};
+use wordlers::game::{GameEvent, Observer, Outcome};
+use wordlers::messages::{locale_from_env, message, set_catalogue, set_locale, MessageId};
+use wordlers::protocol::{run_json_lines, Session};
+use wordlers::server::Server;
+use wordlers::stats::{default_stats_path, GameMode, Stats, StatsRecorder};
+use wordlers::timer::{SystemClock, Timer};
+use wordlers::transcript::{self, read_transcript, RoundSettings, TranscriptRecorder};
+use wordlers::user_input::{InputSource, StdinInput};
+use wordlers::word_source::{MinFrequency, WordSource};
+
+/// Number of attempts to guess a word.
+const N_TRIES: usize = 5;
+/// Port of the game server when none is given.
+const DEFAULT_PORT: u16 = 7878;
+/// Room joined on a game server when none is given.
+const DEFAULT_ROOM: &str = "lobby";
+/// Name of the player when neither `--player`, `--name` nor `$USER` is given.
+const DEFAULT_PLAYER_NAME: &str = "player";
 
 /// A word guessing game.
 ///
 /// The program loads a list of words from a file and chooses a random word of a specified length.
 /// If the user guesses the word correctly, they win. Otherwise, they lose.
+///
+/// Exit status: 0 when the game ends, won or lost; 1 when `dict check` does not find the word;
+/// 2 on invalid arguments; 3 when the game is abandoned, by closing the input or pressing Ctrl-C;
+/// 4 on an invalid word list, dictionary, language pack, stats file or transcript, a length without words,
+/// or a replayed step not matching the rules;
+/// 5 when a file cannot be read or written, or a server cannot be reached; 6 when the input cannot be read;
+/// 70 on an unexpected error.
 #[derive(Parser)]
 #[command(version)]
 struct Args {
     /// Path to the file containing the list of words.
+    /// Defaults to the words of the language pack.
     #[arg(long, short = 'f')]
-    words_file: String,
+    words_file: Option<String>,
+    /// Code of the language pack, providing the words, alphabet, keyboard layout and messages.
+    #[arg(long, default_value = "en")]
+    lang: String,
+    /// Directory containing user language packs, one sub-directory per pack.
+    /// Defaults to `$WORDLERS_LANG_DIR`, or `$HOME/.local/share/wordlers/lang`.
+    #[arg(long)]
+    lang_dir: Option<PathBuf>,
+    /// Language of the user interface messages.
+    /// Defaults to the language of the `LC_ALL`, `LC_MESSAGES` or `LANG` environment variables,
+    /// or else to the language of the pack given by `--lang`.
+    #[arg(long)]
+    ui_lang: Option<String>,
     /// Length of the guess.
     #[arg(long, short = 'n', default_value_t = 5)]
     guess_length: usize,
+    /// Skip invalid lines of the words file instead of failing, and print a summary.
+    #[arg(long)]
+    lenient: bool,
+    /// Minimum frequency of the word to guess, to avoid obscure words. It also applies to the words of `serve`.
+    #[arg(long)]
+    min_frequency: Option<f64>,
+    /// Seed of the random word choice, to replay the same game.
+    #[arg(long)]
+    seed: Option<u64>,
+    /// Race: players take turns at the same terminal to guess the same word,
+    /// and the fastest player who finds it wins. With `serve`, every room is a race.
+    #[arg(long)]
+    race: bool,
+    /// Names of the players of a race, separated by commas.
+    #[arg(
+        long,
+        value_delimiter = ',',
+        default_value = "Player 1,Player 2",
+        requires = "race"
+    )]
+    players: Vec<String>,
+    /// Seconds given to each guess: a guess coming later loses its attempt.
+    #[arg(
+        long,
+        value_name = "SECONDS",
+        value_parser = clap::value_parser!(u64).range(1..),
+        conflicts_with = "race"
+    )]
+    time_limit_per_guess: Option<u64>,
+    /// Blitz: solve as many words as possible in the given number of minutes.
+    #[arg(
+        long,
+        value_name = "MINUTES",
+        value_parser = clap::value_parser!(u64).range(1..),
+        conflicts_with = "race"
+    )]
+    blitz: Option<u64>,
+    /// Hard mode: every guess must fit the feedback and hints received so far.
+    #[arg(long)]
+    hard: bool,
+    /// Survival: words follow each other until one is not found,
+    /// the attempts left unused on a word being added to the next one's.
+    #[arg(long, conflicts_with_all = ["race", "time_limit_per_guess", "blitz"])]
+    survival: bool,
+    /// Name of the player, whose games are recorded in the stats file apart from the other players'.
+    /// Defaults to `$USER`.
+    #[arg(long, conflicts_with = "race")]
+    player: Option<String>,
+    /// File keeping the games of every player.
+    /// Defaults to `$WORDLERS_STATS_FILE`, or `$HOME/.local/share/wordlers/stats.json`.
+    #[arg(long)]
+    stats_file: Option<PathBuf>,
+    /// File to append a transcript of each round to, as JSON Lines: the word, settings and seed,
+    /// then each guess with its feedback and time. It can be played again with `replay`.
+    #[arg(long, value_name = "FILE")]
+    transcript: Option<PathBuf>,
+    /// How the game talks to the player: a coloured board for humans,
+    /// or JSON requests and responses, one per line, for programs.
+    #[arg(long, value_enum, default_value_t = Protocol::Text)]
+    protocol: Protocol,
+    /// List the language packs embedded in the binary, with word counts per length.
+    #[cfg(feature = "builtin-dictionary")]
+    #[arg(long)]
+    list_builtin_dictionaries: bool,
+    #[command(subcommand)]
+    command: Option<Command>,
 }
 
-fn main() {
-    let args = Args::parse();
+/// How the game talks to the player.
+#[derive(Clone, Copy, PartialEq, ValueEnum)]
+enum Protocol {
+    /// A coloured board and prompts, for humans.
+    Text,
+    /// One JSON request per line on stdin, one JSON response per line on stdout.
+    Json,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Help solving a puzzle played elsewhere by typing each guess and the feedback received.
+    Assist,
+    /// Host rooms on the local network, where several players guess the same word at the same time.
+    /// The words are chosen as for a game, and players talk to the server with the JSON protocol.
+    Serve {
+        /// Port to listen on, on every network interface. 0 picks a free port.
+        #[arg(long, default_value_t = DEFAULT_PORT)]
+        port: u16,
+    },
+    /// Join a room of a server started with `serve`, and play its word.
+    Join {
+        /// Address of the server, as `host:port`.
+        address: String,
+        /// Room to join. It is created if it does not exist.
+        #[arg(long, default_value = DEFAULT_ROOM)]
+        room: String,
+        /// Name shown to the other players. Defaults to the name given by `--player`, or `$USER`.
+        #[arg(long)]
+        name: Option<String>,
+    },
+    /// Rank the players whose games are recorded in the stats file,
+    /// by win rate, then fewer guesses on average, then best streak.
+    Leaderboard {
+        /// Only count the games with words of this length.
+        #[arg(long)]
+        length: Option<usize>,
+        /// Only count the games played in this mode: classic, timed, blitz, survival or race.
+        #[arg(long)]
+        mode: Option<GameMode>,
+    },
+    /// Play again the rounds of a transcript written with `--transcript`, step by step,
+    /// checking each feedback against the rules. Exits with status 4 if one does not match.
+    Replay {
+        /// Path to the transcript.
+        file: PathBuf,
+        /// Milliseconds to wait after each step.
+        #[arg(long, default_value_t = 0)]
+        delay: u64,
+    },
+    /// Inspect a words file.
+    Dict {
+        #[command(subcommand)]
+        command: DictCommand,
+    },
+}
+
+#[derive(Subcommand)]
+enum DictCommand {
+    /// Report word counts per length, letter frequencies by position, duplicate and invalid entries.
+    Info {
+        /// Path to the words file.
+        file: String,
+    },
+    /// Check whether a word is in a words file. Exits with status 1 if it is not.
+    Check {
+        /// Path to the words file.
+        file: String,
+        /// Word to look for.
+        word: String,
+    },
+    /// Merge words files into a sorted, deduplicated list, normalising and filtering their words.
+    Build {
+        /// Paths to the words files to merge.
+        #[arg(required = true)]
+        inputs: Vec<String>,
+        /// Path to the file to write, `-` for the standard output.
+        #[arg(long, short = 'o', default_value = STDOUT_PATH)]
+        output: String,
+        /// Minimum length of the words.
+        #[arg(long)]
+        min_length: Option<usize>,
+        /// Maximum length of the words.
+        #[arg(long)]
+        max_length: Option<usize>,
+        /// Characters the words must be made of, e.g. `ABCDEFGHIJKLMNOPQRSTUVWXYZ`.
+        #[arg(long)]
+        alphabet: Option<String>,
+        /// Regular expression of words to drop, matched case-insensitively. May be repeated.
+        #[arg(long)]
+        exclude_regex: Vec<String>,
+        /// Minimum frequency of the words.
+        #[arg(long)]
+        min_frequency: Option<f64>,
+        /// Path to a words file listing words to drop, e.g. profanity. May be repeated.
+        #[arg(long)]
+        blocklist: Vec<String>,
+        /// Remove the accents of the words, e.g. `É` becoming `E`.
+        #[arg(long)]
+        strip_accents: bool,
+    },
+    /// Convert a words file into a compiled dictionary, accepted wherever a words file is.
+    Compile {
+        /// Path to the words file.
+        input: String,
+        /// Path to the compiled dictionary to write.
+        #[arg(long, short = 'o')]
+        output: String,
+    },
+}
+
+/// Prints the language packs embedded in the binary, with their word counts per length.
+#[cfg(feature = "builtin-dictionary")]
+fn list_builtin_dictionaries() {
+    for dictionary in &BUILTIN_DICTIONARIES {
+        let language_pack = dictionary.language_pack();
+        println!("{}: {}", language_pack.code, language_pack.name);
+        let mut lengths: Vec<&usize> = language_pack
+            .answers
+            .keys()
+            .chain(language_pack.guesses.keys())
+            .collect();
+        lengths.sort();
+        lengths.dedup();
+        for length in lengths {
+            println!(
+                "  {}",
+                message(
+                    MessageId::GameBuiltinCounts,
+                    &[
+                        ("length", length),
+                        (
+                            "answers",
+                            &language_pack.answers.get(length).map_or(0, HashMap::len)
+                        ),
+                        (
+                            "guesses",
+                            &language_pack.guesses.get(length).map_or(0, HashMap::len)
+                        ),
+                    ]
+                )
+            );
+        }
+    }
+}
+
+/// Loads the words file given by the user, or the words of the language pack if there is none.
+/// A compiled dictionary is used in place.
+///
+/// # Errors
+/// Returns an error if the words file cannot be read or, unless `--lenient` is given, is invalid.
+fn load_all_words(args: &Args, language_pack: &LanguagePack) -> Result<LoadedWords, AppError> {
+    let Some(words_file) = &args.words_file else {
+        return Ok(LoadedWords::List(language_pack.answers.clone()));
+    };
+
+    let (all_words, skipped_lines) = load_word_source(words_file, args.lenient)
+        .map_err(|err| AppError::from_file(words_file, err))?;
+    print_skipped_lines(&skipped_lines);
+    Ok(all_words)
+}
+
+/// Selects the language of the user interface: a builtin catalogue, or the messages of a user language pack.
+/// Messages stay in the default language if there is none for `ui_lang`.
+fn select_ui_lang(ui_lang: &str, lang_dirs: &[PathBuf]) {
+    if set_locale(ui_lang) {
+        return;
+    }
+    if let Some(catalogue) = find_messages(ui_lang, lang_dirs) {
+        set_catalogue(catalogue);
+    }
+}
+
+/// Returns the dictionaries a guess must be found in: the `accepted_words` and the answers and guesses
+/// of the `language_pack`. A pack without a guess list accepts any word made of its alphabet, so that none is returned.
+fn guess_dictionaries<'a>(
+    accepted_words: &'a (dyn WordSource + Sync),
+    language_pack: &'a LanguagePack,
+) -> Vec<&'a (dyn WordSource + Sync)> {
+    if language_pack.guesses.is_empty() {
+        return Vec::new();
+    }
+    vec![
+        accepted_words,
+        &language_pack.answers,
+        &language_pack.guesses,
+    ]
+}
+
+/// Runs the assistant mode or a game with the `words`, whatever their storage.
+/// Guesses are checked against the `guess_dictionaries` of the `accepted_words` and the `language_pack`.
+///
+/// # Returns
+/// The exit status.
+///
+/// # Errors
+/// Returns an error if there is no word of the requested length, or the user input cannot be read.
+fn run<W: WordSource + Sync>(
+    args: &Args,
+    words: &W,
+    accepted_words: &(dyn WordSource + Sync),
+    language_pack: &LanguagePack,
+) -> Result<u8, AppError> {
+    if let Some(Command::Assist) = args.command {
+        assist_iteration(
+            words,
+            args.guess_length,
+            &language_pack.alphabet,
+            &mut StdinInput,
+        )?;
+        return Ok(0);
+    }
+
+    let dictionaries = guess_dictionaries(accepted_words, language_pack);
+    let mut rng = args
+        .seed
+        .map_or_else(StdRng::from_entropy, StdRng::seed_from_u64);
+    if let Some(Command::Serve { port }) = args.command {
+        let listener = TcpListener::bind(("0.0.0.0", port))?;
+        println!(
+            "{}",
+            message(
+                MessageId::ServerListening,
+                &[("port", &listener.local_addr()?.port())]
+            )
+        );
+        dictionaries
+            .iter()
+            .fold(
+                Server::new(
+                    words,
+                    &language_pack.alphabet,
+                    args.guess_length,
+                    N_TRIES,
+                    rng,
+                ),
+                |server, words| server.with_dictionary(*words),
+            )
+            .with_race(args.race)
+            .serve(&listener)?;
+        return Ok(0);
+    }
+
+    if args.protocol == Protocol::Json {
+        let mut session = dictionaries.iter().fold(
+            Session::new(
+                words,
+                &language_pack.alphabet,
+                args.guess_length,
+                N_TRIES,
+                rng,
+            ),
+            |session, words| session.with_dictionary(*words),
+        );
+        run_json_lines(&mut session, &mut StdinInput, &mut io::stdout().lock())?;
+        return Ok(0);
+    }
+
+    println!("{}", message(MessageId::GameChoosingWord, &[]));
+    let mut choose_word = || match args.seed {
+        Some(_) => words.choose_random(args.guess_length, &mut rng),
+        None => choose_random_word(words, args.guess_length),
+    };
+    let guess_word = choose_word().map_err(|err| match err {
+        data_loader::Error::NoWordThisLength(length) | data_loader::Error::EmptyWordVec(length) => {
+            AppError::NoWordThisLength(
+                length,
+                words
+                    .lengths()
+                    .into_iter()
+                    .filter(|length| words.count(*length) > 0)
+                    .collect(),
+            )
+        }
+        err => AppError::Data(err),
+    })?;
+
+    let stats_path = stats_path(args);
+    let mut stats = match &stats_path {
+        Some(path) => Stats::load(path)?,
+        None => Stats::default(),
+    };
+    let players = if args.race {
+        args.players.clone()
+    } else {
+        vec![args.player.clone().unwrap_or_else(default_player_name)]
+    };
+    let transcript_file = args
+        .transcript
+        .as_ref()
+        .map(|path| {
+            OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(path)
+                .map_err(|err| AppError::File(path.display().to_string(), err))
+        })
+        .transpose()?;
+    let mut recorders = players
+        .iter()
+        .map(|player| {
+            let transcript = match &transcript_file {
+                Some(file) => Some(TranscriptRecorder::new(
+                    file.try_clone()?,
+                    RoundSettings {
+                        player: player.clone(),
+                        mode: game_mode(args),
+                        hard_mode: args.hard,
+                        seed: args.seed,
+                        lang: language_pack.code.clone(),
+                    },
+                )),
+                None => None,
+            };
+            Ok(PlayerRecorder {
+                stats: StatsRecorder::new(game_mode(args), args.hard),
+                transcript,
+            })
+        })
+        .collect::<Result<Vec<PlayerRecorder>, AppError>>()?;
+
+    let mut input: Box<dyn InputSource> = match InterruptibleStdinInput::new() {
+        Ok(input) => Box::new(input),
+        Err(_) => Box::new(StdinInput),
+    };
+    let rules = Rules {
+        n_tries: N_TRIES,
+        hard_mode: args.hard,
+        dictionaries: dictionaries
+            .iter()
+            .map(|words| *words as &dyn WordSource)
+            .collect(),
+    };
+    let outcome = if args.survival {
+        let mut first_word = Some(guess_word);
+        let (survival_run, outcome) = survival_iteration(
+            &mut || first_word.take().or_else(|| choose_word().ok()),
+            &rules,
+            language_pack,
+            &mut input,
+            &mut recorders[0],
+        )?;
+        let player_stats = stats.player(&players[0]);
+        if player_stats.record_survival(survival_run) {
+            println!("{}", message(MessageId::SurvivalNewBest, &[]));
+        } else if let Some(best) = player_stats.best_survival {
+            println!(
+                "{}",
+                message(
+                    MessageId::SurvivalBest,
+                    &[("words", &best.words), ("score", &best.score)]
+                )
+            );
+        }
+        Ok(outcome)
+    } else if args.time_limit_per_guess.is_some() || args.blitz.is_some() {
+        let mut timer = Timer::new(
+            &SystemClock,
+            args.time_limit_per_guess.map(Duration::from_secs),
+            args.blitz.map(|minutes| Duration::from_secs(minutes * 60)),
+        );
+        let mut first_word = Some(guess_word);
+        timed_iteration(
+            &mut || first_word.take().or_else(|| choose_word().ok()),
+            &rules,
+            language_pack,
+            &mut input,
+            &mut timer,
+            &mut recorders[0],
+        )
+    } else if args.race {
+        race_iteration(
+            &guess_word,
+            &rules,
+            language_pack,
+            &players,
+            &mut input,
+            recorders
+                .iter_mut()
+                .map(|recorder| recorder as &mut dyn Observer)
+                .collect(),
+        )
+    } else {
+        game_iteration(
+            &guess_word,
+            &rules,
+            language_pack,
+            &mut input,
+            vec![&mut recorders[0]],
+        )
+        .map(|result| result.outcome)
+    }?;
+
+    let mut transcript_result = Ok(());
+    for (player, recorder) in players.iter().zip(recorders) {
+        stats
+            .player(player)
+            .rounds
+            .extend_from_slice(recorder.stats.records());
+        if let (Some(transcript), Some(path)) = (recorder.transcript, &args.transcript) {
+            if let Err(err) = transcript.finish() {
+                transcript_result = Err(AppError::File(path.display().to_string(), err));
+            }
+        }
+    }
+    if let Some(path) = &stats_path {
+        stats.save(path)?;
+    }
+    transcript_result?;
+    Ok(exit_status(outcome))
+}
+
+/// Records the rounds of a player: in the stats, and in the transcript if one is asked for.
+struct PlayerRecorder {
+    stats: StatsRecorder,
+    transcript: Option<TranscriptRecorder<File>>,
+}
+
+impl Observer for PlayerRecorder {
+    fn notify(&mut self, event: &GameEvent) {
+        self.stats.notify(event);
+        if let Some(transcript) = &mut self.transcript {
+            transcript.notify(event);
+        }
+    }
+}
+
+/// Returns the path of the stats file given by `--stats-file`, or the default one.
+fn stats_path(args: &Args) -> Option<PathBuf> {
+    args.stats_file.clone().or_else(default_stats_path)
+}
 
-    println!("Loading words.");
-    let all_words = load_words_file(&args.words_file).unwrap();
-    println!("Choosing random word.");
-    let guess_word = choose_random_word(&all_words, args.guess_length).unwrap();
+/// Returns the name of the player when none is given: `$USER`, or `DEFAULT_PLAYER_NAME`.
+fn default_player_name() -> String {
+    env::var("USER").unwrap_or_else(|_| String::from(DEFAULT_PLAYER_NAME))
+}
 
-    if game_iteration(&guess_word, 5) {
-        println!("Congratulations, the word was {guess_word}, you won!");
+/// Returns the mode of the game asked for by the `args`, as recorded in the stats.
+fn game_mode(args: &Args) -> GameMode {
+    if args.survival {
+        GameMode::Survival
+    } else if args.blitz.is_some() {
+        GameMode::Blitz
+    } else if args.time_limit_per_guess.is_some() {
+        GameMode::Timed
+    } else if args.race {
+        GameMode::Race
     } else {
-        println!("You lost, the word was {guess_word}.");
+        GameMode::Classic
+    }
+}
+
+/// Prints the players of the stats file at `stats_path` ranked on the games of `word_length` characters
+/// played in `mode`, `None` matching any.
+///
+/// # Errors
+/// Returns an error if the stats file cannot be read or is invalid.
+fn print_leaderboard(
+    stats_path: Option<&Path>,
+    word_length: Option<usize>,
+    mode: Option<GameMode>,
+) -> Result<(), AppError> {
+    let stats = match stats_path {
+        Some(path) => Stats::load(path)?,
+        None => Stats::default(),
+    };
+    let entries = stats.leaderboard(word_length, mode);
+    if entries.is_empty() {
+        println!("{}", message(MessageId::LeaderboardEmpty, &[]));
+    }
+    for (rank, entry) in (1..).zip(&entries) {
+        let average = entry
+            .average_guesses
+            .map_or_else(|| String::from("-"), |average| format!("{average:.2}"));
+        println!(
+            "{}",
+            message(
+                MessageId::LeaderboardEntry,
+                &[
+                    ("rank", &rank),
+                    ("player", &entry.player),
+                    ("win_rate", &format!("{:.0}", entry.win_rate * 100.0)),
+                    ("played", &entry.played),
+                    ("average", &average),
+                    ("streak", &entry.best_streak),
+                ]
+            )
+        );
+    }
+    Ok(())
+}
+
+/// Plays again the rounds of the transcript at `path`, waiting `delay` after each step,
+/// with the language pack each round was played with, or the `language_pack` if it cannot be found.
+///
+/// # Errors
+/// Returns an error if the transcript cannot be read or is invalid, or if a step does not match the rules.
+fn replay_transcript(
+    path: &Path,
+    delay: Duration,
+    language_pack: &LanguagePack,
+    lang_dirs: &[PathBuf],
+) -> Result<(), AppError> {
+    let path_name = path.display().to_string();
+    let file = File::open(path).map_err(|err| AppError::File(path_name.clone(), err))?;
+    let rounds = read_transcript(BufReader::new(file), &path_name)?;
+
+    let mut language_packs: HashMap<String, LanguagePack> = HashMap::new();
+    let mut mismatches = 0;
+    for (number, round) in (1..).zip(&rounds) {
+        let lang = &round.settings.lang;
+        if *lang != language_pack.code && !language_packs.contains_key(lang) {
+            if let Ok(round_language_pack) = find_language_pack(lang, lang_dirs) {
+                language_packs.insert(lang.clone(), round_language_pack);
+            }
+        }
+        let round_language_pack = language_packs.get(lang).unwrap_or(language_pack);
+        mismatches += replay_round(number, round, round_language_pack, delay);
+    }
+    if mismatches > 0 {
+        return Err(transcript::Error::Tampered(mismatches).into());
+    }
+    println!(
+        "{}",
+        message(MessageId::ReplayVerified, &[("rounds", &rounds.len())])
+    );
+    Ok(())
+}
+
+/// Returns the exit status of a game ending with the `outcome`.
+fn exit_status(outcome: Outcome) -> u8 {
+    if outcome == Outcome::Abandoned {
+        EXIT_ABANDONED
+    } else {
+        0
+    }
+}
+
+/// Runs a `dict` subcommand.
+///
+/// # Returns
+/// The exit status.
+///
+/// # Errors
+/// Returns an error if a file cannot be read or written, or an argument is invalid.
+fn run_dict(command: &DictCommand) -> Result<u8, AppError> {
+    match command {
+        DictCommand::Info { file } => dict_info(file)?,
+        DictCommand::Check { file, word } => {
+            if !dict_check(file, word)? {
+                return Ok(EXIT_NOT_FOUND);
+            }
+        }
+        DictCommand::Build {
+            inputs,
+            output,
+            min_length,
+            max_length,
+            alphabet,
+            exclude_regex,
+            min_frequency,
+            blocklist,
+            strip_accents,
+        } => {
+            let options = BuildOptions {
+                min_length: *min_length,
+                max_length: *max_length,
+                alphabet: alphabet
+                    .as_ref()
+                    .map(|alphabet| alphabet.to_uppercase().chars().collect()),
+                min_frequency: *min_frequency,
+                strip_accents: *strip_accents,
+                ..BuildOptions::default()
+            };
+            dict_build(inputs, output, blocklist, exclude_regex, options)?;
+        }
+        DictCommand::Compile { input, output } => dict_compile(input, output)?,
+    }
+    Ok(0)
+}
+
+/// Runs the program once the user interface language is selected.
+///
+/// # Returns
+/// The exit status.
+///
+/// # Errors
+/// Returns the error that stopped the program.
+fn try_main(args: &Args, lang_dirs: &[PathBuf]) -> Result<u8, AppError> {
+    #[cfg(feature = "builtin-dictionary")]
+    if args.list_builtin_dictionaries {
+        list_builtin_dictionaries();
+        return Ok(0);
+    }
+
+    if let Some(Command::Dict { command }) = &args.command {
+        return run_dict(command);
+    }
+    if let Some(Command::Leaderboard { length, mode }) = &args.command {
+        print_leaderboard(stats_path(args).as_deref(), *length, *mode)?;
+        return Ok(0);
+    }
+
+    if args.protocol == Protocol::Text {
+        println!("{}", message(MessageId::GameLoadingWords, &[]));
+    }
+    let language_pack = find_language_pack(&args.lang, lang_dirs)?;
+
+    if let Some(Command::Join {
+        address,
+        room,
+        name,
+    }) = &args.command
+    {
+        let name = name
+            .clone()
+            .or_else(|| args.player.clone())
+            .unwrap_or_else(default_player_name);
+        let outcome = join_game(address, room, &name, &language_pack)?;
+        return Ok(exit_status(outcome));
+    }
+    if let Some(Command::Replay { file, delay }) = &args.command {
+        replay_transcript(
+            file,
+            Duration::from_millis(*delay),
+            &language_pack,
+            lang_dirs,
+        )?;
+        return Ok(0);
+    }
+
+    let all_words = load_all_words(args, &language_pack)?;
+    match (args.min_frequency, &args.command) {
+        (Some(min_frequency), None | Some(Command::Serve { .. })) => run(
+            args,
+            &MinFrequency::new(&all_words, min_frequency),
+            &all_words,
+            &language_pack,
+        ),
+        _ => run(args, &all_words, &all_words, &language_pack),
+    }
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+    let lang_dirs = args
+        .lang_dir
+        .clone()
+        .map_or_else(default_lang_dirs, |lang_dir| vec![lang_dir]);
+    let ui_lang = args
+        .ui_lang
+        .clone()
+        .or_else(locale_from_env)
+        .unwrap_or_else(|| args.lang.clone());
+    select_ui_lang(&ui_lang, &lang_dirs);
+
+    match try_main(&args, &lang_dirs) {
+        Ok(code) => ExitCode::from(code),
+        Err(err) => {
+            eprintln!("{err}");
+            ExitCode::from(err.exit_code())
+        }
     }
 }
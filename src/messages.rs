@@ -0,0 +1,735 @@
+use crate::data_loader::language_pack::parse_key_values;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::{OnceLock, RwLock};
+
+/// Code of the catalogue used when a message is missing from the selected one.
+pub const DEFAULT_LOCALE: &str = "en";
+
+/// The message catalogues embedded in the binary, as `(locale, content)`.
+/// They are also the `messages.txt` files of the bundled language packs.
+const BUILTIN_CATALOGUES: [(&str, &str); 4] = [
+    (
+        "en",
+        include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/data/en/messages.txt")),
+    ),
+    (
+        "de",
+        include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/data/de/messages.txt")),
+    ),
+    (
+        "es",
+        include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/data/es/messages.txt")),
+    ),
+    (
+        "fr",
+        include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/data/fr/messages.txt")),
+    ),
+];
+
+/// A catalogue mapping message IDs to their localised template.
+pub type Catalogue = HashMap<String, String>;
+
+/// The catalogue selected by `set_locale` or `set_catalogue`, `None` meaning the default one.
+static SELECTED_CATALOGUE: RwLock<Option<Catalogue>> = RwLock::new(None);
+
+/// Identifies a user-facing message. Each ID maps to a stable key of the catalogues,
+/// and its documentation lists the parameters of its template in parentheses.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MessageId {
+    /// Announces that the words are being loaded.
+    GameLoadingWords,
+    /// Announces that the word to guess is being chosen.
+    GameChoosingWord,
+    /// Asks for the next guess.
+    GamePrompt,
+    /// Heads the board of the guesses so far.
+    GameCurrentTries,
+    /// The word was found. (`word`)
+    GameWin,
+    /// Every attempt was used without finding the word. (`word`)
+    GameLose,
+    /// The user input could not be read, and is read again. (`error`)
+    GameReadError,
+    /// The user input failed too many times in a row. (`max`)
+    GameTooManyIoErrors,
+    /// Heads the invalid lines skipped while loading a words file. (`count`)
+    GameSkippedLines,
+    /// Word counts of a builtin dictionary for one length. (`length`, `answers`, `guesses`)
+    GameBuiltinCounts,
+    /// A guess has the wrong length. (`expected`, `given`)
+    UserInputUnexpectedLength,
+    /// A guess has characters outside the alphabet. (`characters`)
+    UserInputNotAlphabetic,
+    /// A line of a words file has characters outside the alphabet. (`path`, `line`, `characters`)
+    DataLoaderNotAlphabetic,
+    /// There is no word of the requested length. (`length`)
+    DataLoaderNoWordThisLength,
+    /// The words of the requested length were all removed, e.g. by `--min-frequency`. (`length`)
+    DataLoaderEmptyWordVec,
+    /// A line of a words file has an invalid frequency or rank. (`path`, `line`, `value`)
+    DataLoaderInvalidFrequency,
+    /// A words file uses a compression format disabled at build time. (`path`, `format`)
+    DataLoaderUnsupportedCompression,
+    /// No language pack has the requested code. (`code`, `available`)
+    LanguagePackUnknownLanguage,
+    /// A file of a language pack lacks a required key. (`path`, `key`)
+    LanguagePackMissingKey,
+    /// A line of a language pack file is not `key = value`. (`path`, `line`)
+    LanguagePackMalformedLine,
+    /// A word of a language pack has characters outside its alphabet. (`word`, `code`)
+    LanguagePackOutsideAlphabet,
+    /// A feedback pattern has an unknown character. (`character`, `position`)
+    PatternUnknownCharacter,
+    /// A feedback pattern has the wrong length. (`expected`, `given`)
+    PatternUnexpectedLength,
+    /// A feedback pattern gives the state of a character that was not tried. (`position`)
+    PatternNotTried,
+    /// A feedback given to the solver has the wrong length. (`expected`, `given`)
+    SolverUnexpectedLength,
+    /// A feedback places another character at a known position. (`position`, `known`, `new`)
+    SolverConflictingGood,
+    /// A feedback marks a character as both correct and incorrect. (`character`, `position`)
+    SolverExcludedGood,
+    /// A feedback gives a count of a character contradicting earlier feedback. (`character`)
+    SolverImpossibleCount,
+    /// A feedback requires more characters than the word has. (`required`, `length`)
+    SolverTooManyCharacters,
+    /// A feedback has characters that were not tried.
+    SolverNotTried,
+    /// Explains how to type the guesses and their feedback in the assistant.
+    AssistIntro,
+    /// Explains how to stop the assistant.
+    AssistStop,
+    /// Asks for the next guess and its feedback.
+    AssistPrompt,
+    /// A line of the assistant is not a guess followed by its feedback.
+    AssistMalformedLine,
+    /// A feedback contradicts the earlier ones. (`error`)
+    AssistInconsistent,
+    /// No word of the list fits the feedback.
+    AssistNoCandidate,
+    /// A single word fits the feedback. (`word`)
+    AssistFound,
+    /// Heads the words fitting the feedback. (`count`)
+    AssistCandidates,
+    /// The suggested next guesses. (`suggestions`)
+    AssistSuggestions,
+    /// Heads the word counts per length of `dict info`.
+    DictCountsHeader,
+    /// The number of words of a length. (`length`, `count`)
+    DictCount,
+    /// The total number of words. (`count`)
+    DictTotal,
+    /// Heads the most frequent letters by position. (`length`)
+    DictPositionsHeader,
+    /// The most frequent letters at a position. (`position`, `letters`)
+    DictPosition,
+    /// Heads the duplicate entries. (`count`)
+    DictDuplicates,
+    /// A duplicate entry. (`line`, `word`)
+    DictDuplicate,
+    /// Heads the invalid entries. (`count`)
+    DictInvalid,
+    /// `dict check` found the word. (`word`, `path`, `length`, `frequency`)
+    DictPresent,
+    /// `dict check` did not find the word. (`word`, `path`)
+    DictAbsent,
+    /// `dict build` wrote its word list. (`count`, `path`)
+    DictBuilt,
+    /// A compiled dictionary is truncated or fails its checksum. (`path`)
+    DataLoaderCorruptedDictionary,
+    /// A compiled dictionary has an unknown format version. (`path`, `version`)
+    DataLoaderUnsupportedDictionaryVersion,
+    /// The words to compile have too many distinct characters. (`count`)
+    DataLoaderAlphabetTooLarge,
+    /// `dict compile` wrote its dictionary. (`count`, `path`)
+    DictCompiled,
+    /// Explains how to ask for a hint.
+    GameHintHelp,
+    /// A hint revealed a letter. (`position`, `character`)
+    GameHint,
+    /// Every letter is already known, so there is no hint.
+    GameNoHint,
+    /// The game was abandoned. (`word`)
+    GameAbandoned,
+    /// There is no word of the requested length, with the lengths available. (`length`, `lengths`)
+    ErrorNoWordThisLength,
+    /// A file cannot be read or written. (`path`, `error`)
+    ErrorFile,
+    /// Another I/O operation failed. (`error`)
+    ErrorIo,
+    /// A line of the JSON protocol is not a valid request. (`error`)
+    ProtocolInvalidRequest,
+    /// A request needs a game in progress.
+    ProtocolNoGame,
+    /// A `join` request was sent outside a server.
+    ProtocolServerOnly,
+    /// A `new_game` request was sent to a server.
+    ServerJoinFirst,
+    /// Another player of the room has the same name. (`player`)
+    ServerNameTaken,
+    /// The server is ready. (`port`)
+    ServerListening,
+    /// The user joined a room. (`room`)
+    ServerJoined,
+    /// Another player joined the room. (`player`)
+    ServerPlayerJoined,
+    /// Another player found the word. (`player`, `attempts`, `seconds`)
+    ServerPlayerWon,
+    /// Another player did not find the word. (`player`)
+    ServerPlayerLost,
+    /// Another player left the game. (`player`)
+    ServerPlayerAbandoned,
+    /// The user finished before the other players.
+    ServerWaiting,
+    /// Every player of the room finished. (`word`)
+    ServerRoundOver,
+    /// The server closed the connection before the end of the game.
+    ServerDisconnected,
+    /// The number of guesses and the time taken by a round. (`attempts`, `seconds`)
+    GameSummary,
+    /// Announces whose turn it is in a race. (`player`)
+    RaceTurn,
+    /// Heads the standings of a race.
+    RaceResults,
+    /// A player of the race who found the word. (`rank`, `player`, `attempts`, `seconds`)
+    RaceStandingWon,
+    /// A player of the race who did not find the word. (`rank`, `player`)
+    RaceStandingLost,
+    /// Announces the winner of a race. (`player`)
+    RaceWinner,
+    /// Nobody found the word of a race.
+    RaceNoWinner,
+    /// The current player of a race found the word.
+    RaceFound,
+    /// The current player of a race used every attempt.
+    RaceNotFound,
+    /// Reveals the word of a race. (`word`)
+    RaceWord,
+    /// A guess came too late and lost its attempt.
+    TimerTimeOut,
+    /// The time left for the guess or the run. (`time`)
+    TimerRemaining,
+    /// Announces the next word of a blitz. (`number`)
+    BlitzWord,
+    /// The time of a blitz is over.
+    BlitzTimeUp,
+    /// The words solved in a blitz. (`solved`, `attempts`)
+    BlitzSummary,
+    /// The stats file cannot be understood. (`path`, `error`)
+    StatsMalformed,
+    /// Announces the next word of a survival run. (`number`, `attempts`)
+    SurvivalWord,
+    /// The words found in a survival run. (`words`, `score`)
+    SurvivalSummary,
+    /// A survival run beat the best one.
+    SurvivalNewBest,
+    /// The best survival run. (`words`, `score`)
+    SurvivalBest,
+    /// In hard mode, a guess does not fit the feedback and hints. (`guess`)
+    UserInputHardMode,
+    /// The score of a round. (`score`, `hints`)
+    GameScore,
+    /// The score of a round played in hard mode. (`score`, `hints`)
+    GameScoreHard,
+    /// A game mode filter is unknown. (`mode`, `available`)
+    StatsUnknownMode,
+    /// A player of the leaderboard. (`rank`, `player`, `win_rate`, `played`, `average`, `streak`)
+    LeaderboardEntry,
+    /// The stats file has no matching game.
+    LeaderboardEmpty,
+    /// A line of a transcript cannot be understood. (`line`, `path`, `error`)
+    TranscriptMalformed,
+    /// A step of a transcript comes before any round. (`line`, `path`)
+    TranscriptMissingRound,
+    /// Steps of a transcript do not match the rules. (`count`)
+    TranscriptTampered,
+    /// A stored feedback differs from the computed one. (`guess`, `stored`, `computed`)
+    TranscriptFeedbackMismatch,
+    /// A stored guess is rejected by the rules. (`guess`, `error`)
+    TranscriptRejected,
+    /// A stored guess comes after the end of its round. (`guess`)
+    TranscriptAfterEnd,
+    /// A stored hint differs from the rules. (`character`, `position`)
+    TranscriptHintMismatch,
+    /// A stored ending differs from the rules. (`stored`, `computed`)
+    TranscriptStatusMismatch,
+    /// Heads a replayed round. (`number`, `player`, `mode`, `seed`)
+    ReplayRound,
+    /// Every step of the replayed transcript matches the rules. (`rounds`)
+    ReplayVerified,
+    /// A guess is not in the words or the guess list. (`guess`)
+    UserInputNotInDictionary,
+    /// The server cannot be reached. (`address`, `error`)
+    ErrorConnection,
+}
+
+impl MessageId {
+    /// Every message ID, each one being expected in every catalogue.
+    pub const ALL: [MessageId; 113] = [
+        MessageId::GameLoadingWords,
+        MessageId::GameChoosingWord,
+        MessageId::GamePrompt,
+        MessageId::GameCurrentTries,
+        MessageId::GameWin,
+        MessageId::GameLose,
+        MessageId::GameReadError,
+        MessageId::GameTooManyIoErrors,
+        MessageId::GameSkippedLines,
+        MessageId::GameBuiltinCounts,
+        MessageId::UserInputUnexpectedLength,
+        MessageId::UserInputNotAlphabetic,
+        MessageId::DataLoaderNotAlphabetic,
+        MessageId::DataLoaderNoWordThisLength,
+        MessageId::DataLoaderEmptyWordVec,
+        MessageId::DataLoaderInvalidFrequency,
+        MessageId::DataLoaderUnsupportedCompression,
+        MessageId::LanguagePackUnknownLanguage,
+        MessageId::LanguagePackMissingKey,
+        MessageId::LanguagePackMalformedLine,
+        MessageId::LanguagePackOutsideAlphabet,
+        MessageId::PatternUnknownCharacter,
+        MessageId::PatternUnexpectedLength,
+        MessageId::PatternNotTried,
+        MessageId::SolverUnexpectedLength,
+        MessageId::SolverConflictingGood,
+        MessageId::SolverExcludedGood,
+        MessageId::SolverImpossibleCount,
+        MessageId::SolverTooManyCharacters,
+        MessageId::SolverNotTried,
+        MessageId::AssistIntro,
+        MessageId::AssistStop,
+        MessageId::AssistPrompt,
+        MessageId::AssistMalformedLine,
+        MessageId::AssistInconsistent,
+        MessageId::AssistNoCandidate,
+        MessageId::AssistFound,
+        MessageId::AssistCandidates,
+        MessageId::AssistSuggestions,
+        MessageId::DictCountsHeader,
+        MessageId::DictCount,
+        MessageId::DictTotal,
+        MessageId::DictPositionsHeader,
+        MessageId::DictPosition,
+        MessageId::DictDuplicates,
+        MessageId::DictDuplicate,
+        MessageId::DictInvalid,
+        MessageId::DictPresent,
+        MessageId::DictAbsent,
+        MessageId::DictBuilt,
+        MessageId::DataLoaderCorruptedDictionary,
+        MessageId::DataLoaderUnsupportedDictionaryVersion,
+        MessageId::DataLoaderAlphabetTooLarge,
+        MessageId::DictCompiled,
+        MessageId::GameHintHelp,
+        MessageId::GameHint,
+        MessageId::GameNoHint,
+        MessageId::GameAbandoned,
+        MessageId::ErrorNoWordThisLength,
+        MessageId::ErrorFile,
+        MessageId::ErrorIo,
+        MessageId::ProtocolInvalidRequest,
+        MessageId::ProtocolNoGame,
+        MessageId::ProtocolServerOnly,
+        MessageId::ServerJoinFirst,
+        MessageId::ServerNameTaken,
+        MessageId::ServerListening,
+        MessageId::ServerJoined,
+        MessageId::ServerPlayerJoined,
+        MessageId::ServerPlayerWon,
+        MessageId::ServerPlayerLost,
+        MessageId::ServerPlayerAbandoned,
+        MessageId::ServerWaiting,
+        MessageId::ServerRoundOver,
+        MessageId::ServerDisconnected,
+        MessageId::GameSummary,
+        MessageId::RaceTurn,
+        MessageId::RaceResults,
+        MessageId::RaceStandingWon,
+        MessageId::RaceStandingLost,
+        MessageId::RaceWinner,
+        MessageId::RaceNoWinner,
+        MessageId::RaceFound,
+        MessageId::RaceNotFound,
+        MessageId::RaceWord,
+        MessageId::TimerTimeOut,
+        MessageId::TimerRemaining,
+        MessageId::BlitzWord,
+        MessageId::BlitzTimeUp,
+        MessageId::BlitzSummary,
+        MessageId::StatsMalformed,
+        MessageId::SurvivalWord,
+        MessageId::SurvivalSummary,
+        MessageId::SurvivalNewBest,
+        MessageId::SurvivalBest,
+        MessageId::UserInputHardMode,
+        MessageId::GameScore,
+        MessageId::GameScoreHard,
+        MessageId::StatsUnknownMode,
+        MessageId::LeaderboardEntry,
+        MessageId::LeaderboardEmpty,
+        MessageId::TranscriptMalformed,
+        MessageId::TranscriptMissingRound,
+        MessageId::TranscriptTampered,
+        MessageId::TranscriptFeedbackMismatch,
+        MessageId::TranscriptRejected,
+        MessageId::TranscriptAfterEnd,
+        MessageId::TranscriptHintMismatch,
+        MessageId::TranscriptStatusMismatch,
+        MessageId::ReplayRound,
+        MessageId::ReplayVerified,
+        MessageId::UserInputNotInDictionary,
+        MessageId::ErrorConnection,
+    ];
+
+    /// Returns the stable key of the message in the catalogues.
+    #[must_use]
+    pub fn key(self) -> &'static str {
+        match self {
+            MessageId::GameLoadingWords => "game.loading_words",
+            MessageId::GameChoosingWord => "game.choosing_word",
+            MessageId::GamePrompt => "game.prompt",
+            MessageId::GameCurrentTries => "game.current_tries",
+            MessageId::GameWin => "game.win",
+            MessageId::GameLose => "game.lose",
+            MessageId::GameReadError => "game.read_error",
+            MessageId::GameTooManyIoErrors => "game.too_many_io_errors",
+            MessageId::GameSkippedLines => "game.skipped_lines",
+            MessageId::GameBuiltinCounts => "game.builtin_counts",
+            MessageId::UserInputUnexpectedLength => "user_input.unexpected_length",
+            MessageId::UserInputNotAlphabetic => "user_input.not_alphabetic",
+            MessageId::DataLoaderNotAlphabetic => "data_loader.not_alphabetic",
+            MessageId::DataLoaderNoWordThisLength => "data_loader.no_word_this_length",
+            MessageId::DataLoaderEmptyWordVec => "data_loader.empty_word_vec",
+            MessageId::DataLoaderInvalidFrequency => "data_loader.invalid_frequency",
+            MessageId::DataLoaderUnsupportedCompression => "data_loader.unsupported_compression",
+            MessageId::LanguagePackUnknownLanguage => "language_pack.unknown_language",
+            MessageId::LanguagePackMissingKey => "language_pack.missing_key",
+            MessageId::LanguagePackMalformedLine => "language_pack.malformed_line",
+            MessageId::LanguagePackOutsideAlphabet => "language_pack.outside_alphabet",
+            MessageId::PatternUnknownCharacter => "pattern.unknown_character",
+            MessageId::PatternUnexpectedLength => "pattern.unexpected_length",
+            MessageId::PatternNotTried => "pattern.not_tried",
+            MessageId::SolverUnexpectedLength => "solver.unexpected_length",
+            MessageId::SolverConflictingGood => "solver.conflicting_good",
+            MessageId::SolverExcludedGood => "solver.excluded_good",
+            MessageId::SolverImpossibleCount => "solver.impossible_count",
+            MessageId::SolverTooManyCharacters => "solver.too_many_characters",
+            MessageId::SolverNotTried => "solver.not_tried",
+            MessageId::AssistIntro => "assist.intro",
+            MessageId::AssistStop => "assist.stop",
+            MessageId::AssistPrompt => "assist.prompt",
+            MessageId::AssistMalformedLine => "assist.malformed_line",
+            MessageId::AssistInconsistent => "assist.inconsistent",
+            MessageId::AssistNoCandidate => "assist.no_candidate",
+            MessageId::AssistFound => "assist.found",
+            MessageId::AssistCandidates => "assist.candidates",
+            MessageId::AssistSuggestions => "assist.suggestions",
+            MessageId::DictCountsHeader => "dict.counts_header",
+            MessageId::DictCount => "dict.count",
+            MessageId::DictTotal => "dict.total",
+            MessageId::DictPositionsHeader => "dict.positions_header",
+            MessageId::DictPosition => "dict.position",
+            MessageId::DictDuplicates => "dict.duplicates",
+            MessageId::DictDuplicate => "dict.duplicate",
+            MessageId::DictInvalid => "dict.invalid",
+            MessageId::DictPresent => "dict.present",
+            MessageId::DictAbsent => "dict.absent",
+            MessageId::DictBuilt => "dict.built",
+            MessageId::DataLoaderCorruptedDictionary => "data_loader.corrupted_dictionary",
+            MessageId::DataLoaderUnsupportedDictionaryVersion => {
+                "data_loader.unsupported_dictionary_version"
+            }
+            MessageId::DataLoaderAlphabetTooLarge => "data_loader.alphabet_too_large",
+            MessageId::DictCompiled => "dict.compiled",
+            MessageId::GameHintHelp => "game.hint_help",
+            MessageId::GameHint => "game.hint",
+            MessageId::GameNoHint => "game.no_hint",
+            MessageId::GameAbandoned => "game.abandoned",
+            MessageId::ErrorNoWordThisLength => "error.no_word_this_length",
+            MessageId::ErrorFile => "error.file",
+            MessageId::ErrorIo => "error.io",
+            MessageId::ProtocolInvalidRequest => "protocol.invalid_request",
+            MessageId::ProtocolNoGame => "protocol.no_game",
+            MessageId::ProtocolServerOnly => "protocol.server_only",
+            MessageId::ServerJoinFirst => "server.join_first",
+            MessageId::ServerNameTaken => "server.name_taken",
+            MessageId::ServerListening => "server.listening",
+            MessageId::ServerJoined => "server.joined",
+            MessageId::ServerPlayerJoined => "server.player_joined",
+            MessageId::ServerPlayerWon => "server.player_won",
+            MessageId::ServerPlayerLost => "server.player_lost",
+            MessageId::ServerPlayerAbandoned => "server.player_abandoned",
+            MessageId::ServerWaiting => "server.waiting",
+            MessageId::ServerRoundOver => "server.round_over",
+            MessageId::ServerDisconnected => "server.disconnected",
+            MessageId::GameSummary => "game.summary",
+            MessageId::RaceTurn => "race.turn",
+            MessageId::RaceResults => "race.results",
+            MessageId::RaceStandingWon => "race.standing_won",
+            MessageId::RaceStandingLost => "race.standing_lost",
+            MessageId::RaceWinner => "race.winner",
+            MessageId::RaceNoWinner => "race.no_winner",
+            MessageId::RaceFound => "race.found",
+            MessageId::RaceNotFound => "race.not_found",
+            MessageId::RaceWord => "race.word",
+            MessageId::TimerTimeOut => "timer.time_out",
+            MessageId::TimerRemaining => "timer.remaining",
+            MessageId::BlitzWord => "blitz.word",
+            MessageId::BlitzTimeUp => "blitz.time_up",
+            MessageId::BlitzSummary => "blitz.summary",
+            MessageId::StatsMalformed => "stats.malformed",
+            MessageId::SurvivalWord => "survival.word",
+            MessageId::SurvivalSummary => "survival.summary",
+            MessageId::SurvivalNewBest => "survival.new_best",
+            MessageId::SurvivalBest => "survival.best",
+            MessageId::UserInputHardMode => "user_input.hard_mode",
+            MessageId::GameScore => "game.score",
+            MessageId::GameScoreHard => "game.score_hard",
+            MessageId::StatsUnknownMode => "stats.unknown_mode",
+            MessageId::LeaderboardEntry => "leaderboard.entry",
+            MessageId::LeaderboardEmpty => "leaderboard.empty",
+            MessageId::TranscriptMalformed => "transcript.malformed",
+            MessageId::TranscriptMissingRound => "transcript.missing_round",
+            MessageId::TranscriptTampered => "transcript.tampered",
+            MessageId::TranscriptFeedbackMismatch => "transcript.feedback_mismatch",
+            MessageId::TranscriptRejected => "transcript.rejected",
+            MessageId::TranscriptAfterEnd => "transcript.after_end",
+            MessageId::TranscriptHintMismatch => "transcript.hint_mismatch",
+            MessageId::TranscriptStatusMismatch => "transcript.status_mismatch",
+            MessageId::ReplayRound => "replay.round",
+            MessageId::ReplayVerified => "replay.verified",
+            MessageId::UserInputNotInDictionary => "user_input.not_in_dictionary",
+            MessageId::ErrorConnection => "error.connection",
+        }
+    }
+}
+
+/// Returns the builtin catalogues by locale, parsed once.
+fn builtin_catalogues() -> &'static HashMap<&'static str, Catalogue> {
+    static CATALOGUES: OnceLock<HashMap<&'static str, Catalogue>> = OnceLock::new();
+    CATALOGUES.get_or_init(|| {
+        BUILTIN_CATALOGUES
+            .iter()
+            .map(
+                |(locale, content)| match parse_key_values(locale, content) {
+                    Ok(catalogue) => (*locale, catalogue),
+                    Err(err) => panic!("Invalid builtin catalogue {locale}: {err}"),
+                },
+            )
+            .collect()
+    })
+}
+
+/// Returns the builtin catalogue of `locale`, if any.
+#[must_use]
+pub fn builtin_catalogue(locale: &str) -> Option<&'static Catalogue> {
+    builtin_catalogues().get(locale)
+}
+
+/// Returns the locales of the builtin catalogues, sorted.
+#[must_use]
+pub fn builtin_locales() -> Vec<&'static str> {
+    let mut locales: Vec<&'static str> = builtin_catalogues().keys().copied().collect();
+    locales.sort_unstable();
+    locales
+}
+
+/// Extracts the language of a POSIX locale such as `fr_FR.UTF-8`.
+///
+/// # Examples
+///
+/// ```
+/// use wordlers::messages::language_of_locale;
+///
+/// assert_eq!(language_of_locale("fr_FR.UTF-8"), Some(String::from("fr")));
+/// assert_eq!(language_of_locale("C"), None);
+/// ```
+#[must_use]
+pub fn language_of_locale(locale: &str) -> Option<String> {
+    let language = locale
+        .split(['_', '.', '@'])
+        .next()
+        .unwrap_or_default()
+        .to_lowercase();
+    if language.is_empty() || language == "c" || language == "posix" {
+        None
+    } else {
+        Some(language)
+    }
+}
+
+/// Returns the language of the user locale, from `LC_ALL`, `LC_MESSAGES` or `LANG` in this order.
+#[must_use]
+pub fn locale_from_env() -> Option<String> {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|variable| std::env::var(variable).ok())
+        .find(|locale| !locale.is_empty())
+        .and_then(|locale| language_of_locale(&locale))
+}
+
+/// Selects the builtin catalogue of `locale` for all following messages.
+///
+/// # Returns
+/// True if there is a builtin catalogue for `locale`, false otherwise, in which case the selection is unchanged.
+pub fn set_locale(locale: &str) -> bool {
+    match builtin_catalogue(locale) {
+        Some(catalogue) => {
+            set_catalogue(catalogue.clone());
+            true
+        }
+        None => false,
+    }
+}
+
+/// Selects a custom `catalogue`, such as the messages of a user language pack, for all following messages.
+/// Messages missing from it are taken from the default catalogue.
+pub fn set_catalogue(catalogue: Catalogue) {
+    if let Ok(mut selected_catalogue) = SELECTED_CATALOGUE.write() {
+        *selected_catalogue = Some(catalogue);
+    }
+}
+
+/// Replaces the `{name}` placeholders of `template` with the `params` in a single pass,
+/// so that braces in the values are kept as they are. Unknown placeholders are left unchanged.
+fn fill_template(template: &str, params: &[(&str, &dyn Display)]) -> String {
+    let mut message = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        message.push_str(&rest[..start]);
+        let placeholder = &rest[start..];
+        let value = placeholder.find('}').and_then(|end| {
+            params
+                .iter()
+                .find(|(name, _)| *name == &placeholder[1..end])
+                .map(|(_, value)| (end, value))
+        });
+        match value {
+            Some((end, value)) => {
+                message.push_str(&value.to_string());
+                rest = &placeholder[end + 1..];
+            }
+            None => {
+                message.push('{');
+                rest = &placeholder[1..];
+            }
+        }
+    }
+    message.push_str(rest);
+    message
+}
+
+/// Formats the message `id` of `catalogue` with `params`,
+/// falling back to the default catalogue, then to the message key.
+#[must_use]
+pub fn format_message(
+    catalogue: Option<&Catalogue>,
+    id: MessageId,
+    params: &[(&str, &dyn Display)],
+) -> String {
+    let template = catalogue
+        .and_then(|catalogue| catalogue.get(id.key()))
+        .or_else(|| builtin_catalogue(DEFAULT_LOCALE).and_then(|catalogue| catalogue.get(id.key())))
+        .map_or(id.key(), String::as_str);
+    fill_template(template, params)
+}
+
+/// Formats the message `id` in the selected locale, replacing its `{name}` placeholders with `params`.
+///
+/// # Examples
+///
+/// ```
+/// use wordlers::messages::{message, MessageId};
+///
+/// assert_eq!(
+///     message(MessageId::GameWin, &[("word", &"CRANE")]),
+///     "Congratulations, the word was CRANE, you won!"
+/// );
+/// ```
+#[must_use]
+pub fn message(id: MessageId, params: &[(&str, &dyn Display)]) -> String {
+    match SELECTED_CATALOGUE.read() {
+        Ok(selected_catalogue) => format_message(selected_catalogue.as_ref(), id, params),
+        Err(_) => format_message(None, id, params),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    /// Returns the names of the placeholders of a template.
+    fn placeholders(template: &str) -> HashSet<&str> {
+        template
+            .split('{')
+            .skip(1)
+            .filter_map(|part| part.split_once('}').map(|(name, _)| name))
+            .collect()
+    }
+
+    #[test]
+    fn test_catalogues_are_complete() {
+        let default_catalogue = builtin_catalogue(DEFAULT_LOCALE).unwrap();
+        assert!(builtin_locales().contains(&"fr"));
+        for locale in builtin_locales() {
+            let catalogue = builtin_catalogue(locale).unwrap();
+            for id in MessageId::ALL {
+                let template = catalogue
+                    .get(id.key())
+                    .unwrap_or_else(|| panic!("Catalogue {locale} lacks {}", id.key()));
+                assert_eq!(
+                    placeholders(template),
+                    placeholders(&default_catalogue[id.key()]),
+                    "Catalogue {locale} has different parameters for {}",
+                    id.key()
+                );
+            }
+            assert_eq!(
+                catalogue.len(),
+                MessageId::ALL.len(),
+                "Catalogue {locale} has unknown keys"
+            );
+        }
+    }
+
+    #[test]
+    fn test_format_message() {
+        let french = builtin_catalogue("fr");
+        assert_eq!(
+            format_message(french, MessageId::GameLose, &[("word", &"CRANE")]),
+            "Vous avez perdu, le mot était CRANE."
+        );
+
+        // Missing messages fall back to the default catalogue
+        let partial = Catalogue::new();
+        assert_eq!(
+            format_message(
+                Some(&partial),
+                MessageId::DataLoaderNoWordThisLength,
+                &[("length", &6)]
+            ),
+            "No word of length 6 found."
+        );
+
+        // Braces in the values are not substituted again
+        assert_eq!(
+            format_message(
+                None,
+                MessageId::UserInputUnexpectedLength,
+                &[("expected", &"{given}"), ("given", &"{x}")]
+            ),
+            "Expected a string of {given} characters, got {x}"
+        );
+    }
+
+    #[test]
+    fn test_language_of_locale() {
+        assert_eq!(language_of_locale("de_DE.UTF-8"), Some(String::from("de")));
+        assert_eq!(language_of_locale("es"), Some(String::from("es")));
+        assert_eq!(language_of_locale("POSIX"), None);
+        assert_eq!(language_of_locale(""), None);
+    }
+}
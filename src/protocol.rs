@@ -0,0 +1,575 @@
+use crate::data_loader::Error;
+use crate::game::{Outcome, Round};
+use crate::game_logic::CharacterState;
+use crate::messages::{message, MessageId};
+use crate::user_input::InputSource;
+use crate::word_source::WordSource;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::io::{self, Write};
+use std::time::Duration;
+
+/// A request of a player program, read as one JSON object per line, e.g. `{"type": "guess", "word": "crane"}`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Request {
+    /// Starts a new game, abandoning the current one.
+    /// The `length` defaults to the one of the session, and a `seed` makes the choice of the word reproducible.
+    NewGame {
+        #[serde(default)]
+        length: Option<usize>,
+        #[serde(default)]
+        seed: Option<u64>,
+    },
+    /// Guesses a `word`.
+    Guess { word: String },
+    /// Reveals a character of the word, as the `?` command of the terminal game.
+    Hint,
+    /// Joins the `room` of a server under the name of the `player`, to guess the word of the room.
+    /// It is only accepted by servers, which choose the words in place of `NewGame`.
+    Join { room: String, player: String },
+    /// Ends the session, abandoning the current game.
+    Quit,
+}
+
+/// Where the current game stands.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GameStatus {
+    /// The game is waiting for guesses.
+    InProgress,
+    /// The word was guessed.
+    Won,
+    /// Every attempt was used.
+    Lost,
+    /// The game was abandoned.
+    Abandoned,
+}
+
+impl GameStatus {
+    /// Returns how the game ended, or `None` if it is in progress.
+    #[must_use]
+    pub fn outcome(self) -> Option<Outcome> {
+        match self {
+            GameStatus::InProgress => None,
+            GameStatus::Won => Some(Outcome::Won),
+            GameStatus::Lost => Some(Outcome::Lost),
+            GameStatus::Abandoned => Some(Outcome::Abandoned),
+        }
+    }
+}
+
+impl From<Option<Outcome>> for GameStatus {
+    fn from(outcome: Option<Outcome>) -> Self {
+        match outcome {
+            None => GameStatus::InProgress,
+            Some(Outcome::Won) => GameStatus::Won,
+            Some(Outcome::Lost) => GameStatus::Lost,
+            Some(Outcome::Abandoned) => GameStatus::Abandoned,
+        }
+    }
+}
+
+/// Why a request failed, for programs to react without parsing the message.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    /// The line is not a valid request.
+    InvalidRequest,
+    /// There is no game in progress to guess or ask a hint in.
+    NoGame,
+    /// The guess has the wrong length, characters outside the alphabet, or is not in the dictionaries.
+    /// It did not use an attempt.
+    InvalidGuess,
+    /// Every character of the word is already known.
+    NoHint,
+    /// There is no word of the requested length.
+    NoWord,
+    /// Another player of the room has the same name.
+    NameTaken,
+}
+
+/// A response to a request, written as one JSON object per line.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Response {
+    /// A game started.
+    GameStarted { length: usize, max_attempts: usize },
+    /// A guess was scored. The `word` is given once the game is over.
+    /// `elapsed_ms` is the total time taken by the guesses so far, in milliseconds.
+    Feedback {
+        guess: String,
+        feedback: Vec<CharacterState>,
+        remaining_attempts: usize,
+        status: GameStatus,
+        elapsed_ms: u64,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        word: Option<String>,
+    },
+    /// A character of the word was revealed, at the 0-based `position`.
+    Hint {
+        position: usize,
+        character: char,
+        remaining_attempts: usize,
+        status: GameStatus,
+    },
+    /// The request failed, and changed nothing.
+    Error { code: ErrorCode, message: String },
+    /// The player joined a `room` of a server, and its game started.
+    Joined {
+        room: String,
+        length: usize,
+        max_attempts: usize,
+    },
+    /// Another `player` joined the room.
+    PlayerJoined { player: String },
+    /// A `player` of the room finished its game, using `attempts` guesses in `time_ms` milliseconds.
+    PlayerFinished {
+        player: String,
+        status: GameStatus,
+        attempts: usize,
+        time_ms: u64,
+    },
+    /// Every player of the room finished guessing the `word`. In a race, the `winner` is given, if any.
+    RoundOver {
+        word: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        winner: Option<String>,
+    },
+    /// The session ended. The `word` of the game it abandoned is given, if any.
+    Goodbye {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        word: Option<String>,
+    },
+}
+
+impl Response {
+    pub(crate) fn error(code: ErrorCode, message: String) -> Self {
+        Response::Error { code, message }
+    }
+}
+
+/// Parses a JSON `line` into a request.
+///
+/// # Errors
+///
+/// Returns the `ErrorCode::InvalidRequest` error to respond if the line is not a valid request.
+pub(crate) fn parse_request(line: &str) -> Result<Request, Response> {
+    serde_json::from_str(line).map_err(|err| {
+        Response::error(
+            ErrorCode::InvalidRequest,
+            message(MessageId::ProtocolInvalidRequest, &[("error", &err)]),
+        )
+    })
+}
+
+/// Returns the `round` if it is in progress.
+///
+/// # Errors
+///
+/// Returns the `ErrorCode::NoGame` error to respond if there is no round, or it is finished.
+pub(crate) fn in_progress<'r, 'a>(
+    round: Option<&'r mut Round<'a>>,
+) -> Result<&'r mut Round<'a>, Response> {
+    round
+        .filter(|round| !round.is_finished())
+        .ok_or_else(|| Response::error(ErrorCode::NoGame, message(MessageId::ProtocolNoGame, &[])))
+}
+
+/// Scores a guess of the `word` in the `round`.
+pub(crate) fn guess_response(round: &mut Round<'_>, word: &str) -> Response {
+    let guess = match round.check(word) {
+        Ok(guess) => guess,
+        Err(err) => return Response::error(ErrorCode::InvalidGuess, err.to_string()),
+    };
+    let feedback = round.guess(&guess).unwrap_or_default();
+    Response::Feedback {
+        guess,
+        feedback,
+        remaining_attempts: round.remaining_attempts(),
+        status: GameStatus::from(round.outcome()),
+        elapsed_ms: millis(round.elapsed()),
+        word: round.is_finished().then(|| String::from(round.word())),
+    }
+}
+
+/// Converts a `duration` to the milliseconds sent in responses.
+#[must_use]
+pub fn millis(duration: Duration) -> u64 {
+    u64::try_from(duration.as_millis()).unwrap_or(u64::MAX)
+}
+
+/// Reveals a character of the word of the `round`.
+pub(crate) fn hint_response(round: &mut Round<'_>) -> Response {
+    match round.hint() {
+        Some((position, character)) => Response::Hint {
+            position,
+            character,
+            remaining_attempts: round.remaining_attempts(),
+            status: GameStatus::from(round.outcome()),
+        },
+        None => Response::error(ErrorCode::NoHint, message(MessageId::GameNoHint, &[])),
+    }
+}
+
+/// Writes a request or a response to the `output` as a line of JSON, and flushes it.
+///
+/// # Errors
+///
+/// Returns an error if the output cannot be written.
+pub fn write_json_line(
+    output: &mut (impl Write + ?Sized),
+    message: &impl Serialize,
+) -> io::Result<()> {
+    serde_json::to_writer(&mut *output, message)?;
+    writeln!(output)?;
+    output.flush()
+}
+
+/// A series of games played by a program through requests and responses, with no display.
+///
+/// # Examples
+///
+/// ```
+/// use rand::rngs::StdRng;
+/// use rand::SeedableRng;
+/// use wordlers::protocol::{GameStatus, Response, Session};
+/// use wordlers::word_source::WordList;
+///
+/// let words = WordList::from(["CRANE"]);
+/// let alphabet: Vec<char> = ('A'..='Z').collect();
+/// let mut session = Session::new(&words, &alphabet, 5, 6, StdRng::seed_from_u64(0));
+/// session.handle_line(r#"{"type": "new_game"}"#);
+/// match session.handle_line(r#"{"type": "guess", "word": "crane"}"#) {
+///     Response::Feedback { status, .. } => assert_eq!(status, GameStatus::Won),
+///     response => panic!("unexpected response {response:?}"),
+/// }
+/// ```
+pub struct Session<'w, W: WordSource, R: Rng> {
+    words: &'w W,
+    alphabet: Vec<char>,
+    default_length: usize,
+    max_attempts: usize,
+    rng: R,
+    dictionaries: Vec<&'w dyn WordSource>,
+    round: Option<Round<'w>>,
+}
+
+impl<'w, W: WordSource, R: Rng> Session<'w, W, R> {
+    /// Creates a session choosing the words among `words` with the `rng`,
+    /// guesses being made of the characters of the `alphabet`.
+    ///
+    /// # Arguments
+    ///
+    /// * `default_length` - The length of the words when a new game request does not give one.
+    /// * `max_attempts` - The number of attempts of each game.
+    #[must_use]
+    pub fn new(
+        words: &'w W,
+        alphabet: &[char],
+        default_length: usize,
+        max_attempts: usize,
+        rng: R,
+    ) -> Self {
+        Session {
+            words,
+            alphabet: alphabet.to_vec(),
+            default_length,
+            max_attempts,
+            rng,
+            dictionaries: Vec::new(),
+            round: None,
+        }
+    }
+
+    /// Only accepts guesses found in one of the dictionaries, `words` being added to them, or the word to guess.
+    /// Without a dictionary, any word of the alphabet is accepted.
+    #[must_use]
+    pub fn with_dictionary(mut self, words: &'w dyn WordSource) -> Self {
+        self.dictionaries.push(words);
+        self
+    }
+
+    /// Parses a JSON `line` and handles the request.
+    ///
+    /// # Returns
+    ///
+    /// The response to the request, or an `ErrorCode::InvalidRequest` error if the line is not a valid request.
+    pub fn handle_line(&mut self, line: &str) -> Response {
+        match parse_request(line) {
+            Ok(request) => self.handle(request),
+            Err(response) => response,
+        }
+    }
+
+    /// Handles a `request`.
+    ///
+    /// # Returns
+    ///
+    /// The response to the request.
+    pub fn handle(&mut self, request: Request) -> Response {
+        match request {
+            Request::NewGame { length, seed } => self.new_game(length, seed),
+            Request::Guess { word } => self.guess(&word),
+            Request::Hint => self.hint(),
+            Request::Join { .. } => Response::error(
+                ErrorCode::InvalidRequest,
+                message(MessageId::ProtocolServerOnly, &[]),
+            ),
+            Request::Quit => Response::Goodbye {
+                word: self.abandon(),
+            },
+        }
+    }
+
+    /// Abandons the game in progress, if any.
+    ///
+    /// # Returns
+    ///
+    /// The word of the abandoned game.
+    fn abandon(&mut self) -> Option<String> {
+        let round = self.round.as_mut().filter(|round| !round.is_finished())?;
+        round.abandon();
+        Some(String::from(round.word()))
+    }
+
+    fn new_game(&mut self, length: Option<usize>, seed: Option<u64>) -> Response {
+        let length = length.unwrap_or(self.default_length);
+        let word = match seed {
+            Some(seed) => self
+                .words
+                .choose_random(length, &mut StdRng::seed_from_u64(seed)),
+            None => self.words.choose_random(length, &mut self.rng),
+        };
+        let word = match word {
+            Ok(word) => word,
+            Err(err @ (Error::NoWordThisLength(_) | Error::EmptyWordVec(_))) => {
+                return Response::error(ErrorCode::NoWord, err.to_string());
+            }
+            Err(err) => return Response::error(ErrorCode::InvalidRequest, err.to_string()),
+        };
+
+        self.abandon();
+        let mut round = self.dictionaries.iter().fold(
+            Round::new(&word, &self.alphabet, self.max_attempts),
+            |round, words| round.with_dictionary(*words),
+        );
+        round.start();
+        self.round = Some(round);
+        Response::GameStarted {
+            length,
+            max_attempts: self.max_attempts,
+        }
+    }
+
+    fn guess(&mut self, word: &str) -> Response {
+        match in_progress(self.round.as_mut()) {
+            Ok(round) => guess_response(round, word),
+            Err(response) => response,
+        }
+    }
+
+    fn hint(&mut self) -> Response {
+        match in_progress(self.round.as_mut()) {
+            Ok(round) => hint_response(round),
+            Err(response) => response,
+        }
+    }
+}
+
+/// Plays a `session` with the JSON requests read from the `input`, one per line,
+/// writing each response to the `output` as a line of JSON. Blank lines are ignored.
+///
+/// It stops after a `Request::Quit`, or when the input is exhausted.
+///
+/// # Errors
+///
+/// Returns an error if the input cannot be read or the output cannot be written.
+pub fn run_json_lines<W: WordSource, R: Rng>(
+    session: &mut Session<'_, W, R>,
+    input: &mut impl InputSource,
+    output: &mut impl Write,
+) -> io::Result<()> {
+    while let Some(line) = input.read_line()? {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let response = session.handle_line(&line);
+        write_json_line(output, &response)?;
+        if let Response::Goodbye { .. } = response {
+            break;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::user_input::ScriptedInput;
+    use crate::word_source::WordList;
+
+    /// Zeroes the elapsed time of a `Feedback` `response`, which depends on the test machine.
+    fn untimed(response: Response) -> Response {
+        match response {
+            Response::Feedback {
+                guess,
+                feedback,
+                remaining_attempts,
+                status,
+                word,
+                ..
+            } => Response::Feedback {
+                guess,
+                feedback,
+                remaining_attempts,
+                status,
+                elapsed_ms: 0,
+                word,
+            },
+            response => response,
+        }
+    }
+
+    #[test]
+    fn test_session() {
+        let words = WordList::from(["CRANE"]);
+        let alphabet: Vec<char> = ('A'..='Z').collect();
+        let mut session = Session::new(&words, &alphabet, 5, 2, StdRng::seed_from_u64(0));
+
+        // Test case 1: Guessing before the game starts
+        assert!(matches!(
+            session.handle(Request::Hint),
+            Response::Error {
+                code: ErrorCode::NoGame,
+                ..
+            }
+        ));
+
+        // Test case 2: A lost game
+        assert_eq!(
+            session.handle_line(r#"{"type": "new_game"}"#),
+            Response::GameStarted {
+                length: 5,
+                max_attempts: 2
+            }
+        );
+        assert!(matches!(
+            session.handle_line(r#"{"type": "guess", "word": "cr4ne"}"#),
+            Response::Error {
+                code: ErrorCode::InvalidGuess,
+                ..
+            }
+        ));
+        assert_eq!(
+            session.handle_line(r#"{"type": "hint"}"#),
+            Response::Hint {
+                position: 0,
+                character: 'C',
+                remaining_attempts: 2,
+                status: GameStatus::InProgress
+            }
+        );
+        session.handle_line(r#"{"type": "guess", "word": "crate"}"#);
+        assert_eq!(
+            untimed(session.handle_line(r#"{"type": "guess", "word": "trace"}"#)),
+            Response::Feedback {
+                guess: String::from("TRACE"),
+                feedback: vec![
+                    CharacterState::NotPresent('T'),
+                    CharacterState::Good('R'),
+                    CharacterState::Good('A'),
+                    CharacterState::Misplaced('C'),
+                    CharacterState::Good('E'),
+                ],
+                remaining_attempts: 0,
+                status: GameStatus::Lost,
+                elapsed_ms: 0,
+                word: Some(String::from("CRANE"))
+            }
+        );
+
+        // Test case 3: Invalid requests and lengths without words
+        assert!(matches!(
+            session.handle_line(r#"{"type": "dance"}"#),
+            Response::Error {
+                code: ErrorCode::InvalidRequest,
+                ..
+            }
+        ));
+        assert!(matches!(
+            session.handle(Request::NewGame {
+                length: Some(7),
+                seed: None
+            }),
+            Response::Error {
+                code: ErrorCode::NoWord,
+                ..
+            }
+        ));
+
+        // Test case 4: Guesses outside the dictionaries are rejected
+        let guesses = WordList::from(["TRACE"]);
+        let mut session = Session::new(&words, &alphabet, 5, 2, StdRng::seed_from_u64(0))
+            .with_dictionary(&words)
+            .with_dictionary(&guesses);
+        session.handle_line(r#"{"type": "new_game"}"#);
+        assert!(matches!(
+            session.handle_line(r#"{"type": "guess", "word": "crate"}"#),
+            Response::Error {
+                code: ErrorCode::InvalidGuess,
+                ..
+            }
+        ));
+        assert!(matches!(
+            session.handle_line(r#"{"type": "guess", "word": "trace"}"#),
+            Response::Feedback {
+                remaining_attempts: 1,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_run_json_lines() {
+        let words = WordList::from(["CRANE"]);
+        let alphabet: Vec<char> = ('A'..='Z').collect();
+        let mut session = Session::new(&words, &alphabet, 5, 6, StdRng::seed_from_u64(0));
+        let mut input = ScriptedInput::from(
+            [
+                r#"{"type": "new_game", "seed": 1}"#,
+                "",
+                r#"{"type": "guess", "word": "CRANE"}"#,
+                r#"{"type": "quit"}"#,
+                r#"{"type": "hint"}"#,
+            ]
+            .map(String::from)
+            .to_vec(),
+        );
+        let mut output = Vec::new();
+
+        // Test case 1: One response per request, until the session ends
+        run_json_lines(&mut session, &mut input, &mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(
+            lines[0],
+            r#"{"type":"game_started","length":5,"max_attempts":6}"#
+        );
+        let (feedback, elapsed) = lines[1].split_once(r#","elapsed_ms":"#).unwrap();
+        assert_eq!(
+            feedback,
+            concat!(
+                r#"{"type":"feedback","guess":"CRANE","feedback":["#,
+                r#"{"state":"good","character":"C"},{"state":"good","character":"R"},"#,
+                r#"{"state":"good","character":"A"},{"state":"good","character":"N"},"#,
+                r#"{"state":"good","character":"E"}],"#,
+                r#""remaining_attempts":5,"status":"won""#
+            )
+        );
+        assert!(elapsed.ends_with(r#","word":"CRANE"}"#));
+        assert_eq!(lines[2], r#"{"type":"goodbye"}"#);
+    }
+}
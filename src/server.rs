@@ -0,0 +1,545 @@
+use crate::game::{rank_race, Outcome, RaceStanding, Round};
+use crate::messages::{message, MessageId};
+use crate::protocol::{
+    guess_response, hint_response, in_progress, millis, parse_request, write_json_line, ErrorCode,
+    GameStatus, Request, Response,
+};
+use crate::user_input::{InputSource, ReaderInput};
+use crate::word_source::WordSource;
+use rand::Rng;
+use std::collections::HashMap;
+use std::io::{self, BufReader, Write};
+use std::net::TcpListener;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::thread;
+use std::time::Duration;
+
+/// Where the responses to a player are written, shared with the room to broadcast what the others do.
+pub type SharedOutput = Arc<Mutex<dyn Write + Send>>;
+
+/// Writes a `response` to a shared `output`.
+fn send(output: &SharedOutput, response: &Response) -> io::Result<()> {
+    let mut output = output.lock().unwrap_or_else(PoisonError::into_inner);
+    write_json_line(&mut *output, response)
+}
+
+/// A player of a room, as seen by the other players.
+struct Player {
+    connection_id: usize,
+    name: String,
+    output: SharedOutput,
+    outcome: Option<Outcome>,
+    attempts: usize,
+    time: Duration,
+}
+
+/// Players guessing the same word. In a race, the fastest player to find it wins.
+struct Room {
+    word: String,
+    race: bool,
+    players: Vec<Player>,
+}
+
+impl Room {
+    /// Sends a `response` to every player of the room.
+    /// Players who cannot be reached are ignored: their connection will end and leave the room.
+    fn broadcast(&self, response: &Response) {
+        for player in &self.players {
+            let _ = send(&player.output, response);
+        }
+    }
+
+    /// Checks whether every player finished guessing the word.
+    fn is_over(&self) -> bool {
+        self.players.iter().all(|player| player.outcome.is_some())
+    }
+
+    /// Returns the winner of the race, if the room is a race and someone found the word.
+    fn winner(&self) -> Option<String> {
+        if !self.race {
+            return None;
+        }
+        let mut standings: Vec<RaceStanding> = self
+            .players
+            .iter()
+            .filter_map(|player| {
+                Some(RaceStanding {
+                    player: player.name.clone(),
+                    outcome: player.outcome?,
+                    attempts: player.attempts,
+                    time: player.time,
+                })
+            })
+            .collect();
+        rank_race(&mut standings).map(|winner| winner.player.clone())
+    }
+
+    /// Records how the `round` of the player of `connection_id` ended, and broadcasts it.
+    /// The word, and the winner of a race, are revealed to everyone once every player finished.
+    fn finish(&mut self, connection_id: usize, round: &Round<'_>) {
+        let Some(outcome) = round.outcome() else {
+            return;
+        };
+        let Some(player) = self
+            .players
+            .iter_mut()
+            .find(|player| player.connection_id == connection_id)
+        else {
+            return;
+        };
+        player.outcome = Some(outcome);
+        player.attempts = round.rows().len();
+        player.time = round.elapsed();
+        let response = Response::PlayerFinished {
+            player: player.name.clone(),
+            status: GameStatus::from(Some(outcome)),
+            attempts: player.attempts,
+            time_ms: millis(player.time),
+        };
+        self.broadcast(&response);
+        if self.is_over() {
+            self.broadcast(&Response::RoundOver {
+                word: self.word.clone(),
+                winner: self.winner(),
+            });
+        }
+    }
+}
+
+/// A connection to the server: a player, and its game once it joined a room.
+struct Connection<'w> {
+    id: usize,
+    output: SharedOutput,
+    room: Option<String>,
+    round: Option<Round<'w>>,
+}
+
+/// Hosts rooms where several players guess the same word at the same time,
+/// each player being told when the others finish.
+///
+/// Players talk to the server with the JSON lines of `wordlers::protocol`, joining a room with `Request::Join`.
+/// A room is created by its first player, and a new word is chosen when someone joins once every player finished.
+pub struct Server<'w, W: WordSource, R: Rng> {
+    words: &'w W,
+    dictionaries: Vec<&'w (dyn WordSource + Sync)>,
+    alphabet: Vec<char>,
+    length: usize,
+    max_attempts: usize,
+    rng: Mutex<R>,
+    rooms: Mutex<HashMap<String, Room>>,
+    next_connection_id: AtomicUsize,
+    race: bool,
+}
+
+impl<'w, W: WordSource, R: Rng> Server<'w, W, R> {
+    /// Creates a server choosing the words of `length` characters among `words` with the `rng`,
+    /// guesses being made of the characters of the `alphabet`, in at most `max_attempts`.
+    #[must_use]
+    pub fn new(
+        words: &'w W,
+        alphabet: &[char],
+        length: usize,
+        max_attempts: usize,
+        rng: R,
+    ) -> Self {
+        Server {
+            words,
+            dictionaries: Vec::new(),
+            alphabet: alphabet.to_vec(),
+            length,
+            max_attempts,
+            rng: Mutex::new(rng),
+            rooms: Mutex::new(HashMap::new()),
+            next_connection_id: AtomicUsize::new(0),
+            race: false,
+        }
+    }
+
+    /// Only accepts guesses found in one of the dictionaries, `words` being added to them, or the word to guess.
+    /// Without a dictionary, any word of the alphabet is accepted.
+    #[must_use]
+    pub fn with_dictionary(mut self, words: &'w (dyn WordSource + Sync)) -> Self {
+        self.dictionaries.push(words);
+        self
+    }
+
+    /// Makes every room a race if `race` is set: once every player finished,
+    /// the fastest player who found the word is announced as the winner, ties being broken by fewer guesses.
+    #[must_use]
+    pub fn with_race(mut self, race: bool) -> Self {
+        self.race = race;
+        self
+    }
+
+    fn rooms(&self) -> MutexGuard<'_, HashMap<String, Room>> {
+        self.rooms.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Accepts players on the `listener`, each in its own thread. It never returns unless the listener fails.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the listener cannot accept connections anymore.
+    pub fn serve(&self, listener: &TcpListener) -> io::Result<()>
+    where
+        W: Sync,
+        R: Send,
+    {
+        thread::scope(|scope| {
+            for stream in listener.incoming() {
+                let stream = stream?;
+                let Ok(reader) = stream.try_clone() else {
+                    continue;
+                };
+                scope.spawn(move || {
+                    let output: SharedOutput = Arc::new(Mutex::new(stream));
+                    let _ =
+                        self.handle_client(&mut ReaderInput::new(BufReader::new(reader)), output);
+                });
+            }
+            Ok(())
+        })
+    }
+
+    /// Plays with a player, reading its requests from the `input` and writing the responses to the `output`,
+    /// until it quits or the input is exhausted. The player then leaves its room.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the input cannot be read or the output cannot be written.
+    pub fn handle_client(
+        &self,
+        input: &mut impl InputSource,
+        output: SharedOutput,
+    ) -> io::Result<()> {
+        let mut connection = Connection {
+            id: self.next_connection_id.fetch_add(1, Ordering::Relaxed),
+            output,
+            room: None,
+            round: None,
+        };
+        let result = self.play(&mut connection, input);
+        self.leave(&mut connection);
+        result
+    }
+
+    fn play(
+        &self,
+        connection: &mut Connection<'w>,
+        input: &mut impl InputSource,
+    ) -> io::Result<()> {
+        while let Some(line) = input.read_line()? {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let response = match parse_request(&line) {
+                Ok(request) => self.handle(connection, request),
+                Err(response) => Some(response),
+            };
+            if let Some(response) = response {
+                send(&connection.output, &response)?;
+                if let Response::Goodbye { .. } = response {
+                    break;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Handles a `request` of the player of the `connection`.
+    ///
+    /// # Returns
+    ///
+    /// The response to send to the player, unless it was already sent.
+    fn handle(&self, connection: &mut Connection<'w>, request: Request) -> Option<Response> {
+        match request {
+            Request::Join { room, player } => self.join(connection, room, player),
+            Request::Guess { word } => {
+                let round = match in_progress(connection.round.as_mut()) {
+                    Ok(round) => round,
+                    Err(response) => return Some(response),
+                };
+                let response = guess_response(round, &word);
+                if round.is_finished() {
+                    let _ = send(&connection.output, &response);
+                    self.finish(connection);
+                    return None;
+                }
+                Some(response)
+            }
+            Request::Hint => Some(match in_progress(connection.round.as_mut()) {
+                Ok(round) => hint_response(round),
+                Err(response) => response,
+            }),
+            Request::NewGame { .. } => Some(Response::error(
+                ErrorCode::InvalidRequest,
+                message(MessageId::ServerJoinFirst, &[]),
+            )),
+            Request::Quit => Some(Response::Goodbye {
+                word: self.leave(connection),
+            }),
+        }
+    }
+
+    /// Records how the game of the `connection` ended in its room.
+    fn finish(&self, connection: &Connection<'_>) {
+        let mut rooms = self.rooms();
+        if let (Some(room), Some(round)) = (
+            connection
+                .room
+                .as_ref()
+                .and_then(|room| rooms.get_mut(room)),
+            &connection.round,
+        ) {
+            room.finish(connection.id, round);
+        }
+    }
+
+    /// Makes the player of the `connection` join a room under the name of `player`, leaving its current room.
+    ///
+    /// # Returns
+    ///
+    /// The error to send to the player, if it could not join.
+    fn join(
+        &self,
+        connection: &mut Connection<'w>,
+        room_name: String,
+        player: String,
+    ) -> Option<Response> {
+        let mut rooms = self.rooms();
+        let name_taken = rooms.get(&room_name).is_some_and(|room| {
+            !room.is_over()
+                && room
+                    .players
+                    .iter()
+                    .any(|known| known.name == player && known.connection_id != connection.id)
+        });
+        if name_taken {
+            return Some(Response::error(
+                ErrorCode::NameTaken,
+                message(MessageId::ServerNameTaken, &[("player", &player)]),
+            ));
+        }
+
+        leave_room(&mut rooms, connection);
+        if rooms.get(&room_name).is_none_or(Room::is_over) {
+            let word = {
+                let mut rng = self.rng.lock().unwrap_or_else(PoisonError::into_inner);
+                self.words.choose_random(self.length, &mut *rng)
+            };
+            match word {
+                Ok(word) => {
+                    rooms.insert(
+                        room_name.clone(),
+                        Room {
+                            word,
+                            race: self.race,
+                            players: Vec::new(),
+                        },
+                    );
+                }
+                Err(err) => return Some(Response::error(ErrorCode::NoWord, err.to_string())),
+            }
+        }
+        let room = rooms.get_mut(&room_name)?;
+        let mut round = self.dictionaries.iter().fold(
+            Round::new(&room.word, &self.alphabet, self.max_attempts),
+            |round, words| round.with_dictionary(*words),
+        );
+        round.start();
+        let _ = send(
+            &connection.output,
+            &Response::Joined {
+                room: room_name.clone(),
+                length: round.word_length(),
+                max_attempts: self.max_attempts,
+            },
+        );
+        room.broadcast(&Response::PlayerJoined {
+            player: player.clone(),
+        });
+        room.players.push(Player {
+            connection_id: connection.id,
+            name: player,
+            output: Arc::clone(&connection.output),
+            outcome: None,
+            attempts: 0,
+            time: Duration::ZERO,
+        });
+        connection.room = Some(room_name);
+        connection.round = Some(round);
+        None
+    }
+
+    /// Makes the player of the `connection` leave its room, abandoning its game if it is in progress.
+    ///
+    /// # Returns
+    ///
+    /// The word of the abandoned game, if any.
+    fn leave(&self, connection: &mut Connection<'w>) -> Option<String> {
+        leave_room(&mut self.rooms(), connection)
+    }
+}
+
+/// Makes the player of the `connection` leave its room among the `rooms`,
+/// abandoning its game if it is in progress. Empty rooms are removed.
+///
+/// # Returns
+///
+/// The word of the abandoned game, if any.
+fn leave_room(
+    rooms: &mut HashMap<String, Room>,
+    connection: &mut Connection<'_>,
+) -> Option<String> {
+    let round = connection.round.take();
+    let room_name = connection.room.take()?;
+    let room = rooms.get_mut(&room_name)?;
+
+    let mut abandoned_word = None;
+    if let Some(mut round) = round.filter(|round| !round.is_finished()) {
+        round.abandon();
+        room.finish(connection.id, &round);
+        abandoned_word = Some(String::from(round.word()));
+    }
+    room.players
+        .retain(|player| player.connection_id != connection.id);
+    if room.players.is_empty() {
+        rooms.remove(&room_name);
+    }
+    abandoned_word
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::word_source::WordList;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    /// Creates a connection writing to a buffer, returned to read what it received.
+    fn connection<'w>(
+        server: &Server<'w, WordList, StdRng>,
+    ) -> (Connection<'w>, Arc<Mutex<Vec<u8>>>) {
+        let buffer = Arc::new(Mutex::new(Vec::new()));
+        let connection = Connection {
+            id: server.next_connection_id.fetch_add(1, Ordering::Relaxed),
+            output: buffer.clone(),
+            room: None,
+            round: None,
+        };
+        (connection, buffer)
+    }
+
+    /// Returns the responses received in the `buffer` since the last call, emptying it.
+    /// The times taken by the players, which depend on the test machine, are zeroed.
+    fn received(buffer: &Arc<Mutex<Vec<u8>>>) -> Vec<Response> {
+        let bytes = std::mem::take(&mut *buffer.lock().unwrap());
+        String::from_utf8(bytes)
+            .unwrap()
+            .lines()
+            .map(|line| match serde_json::from_str(line).unwrap() {
+                Response::PlayerFinished {
+                    player,
+                    status,
+                    attempts,
+                    ..
+                } => Response::PlayerFinished {
+                    player,
+                    status,
+                    attempts,
+                    time_ms: 0,
+                },
+                response => response,
+            })
+            .collect()
+    }
+
+    fn join(room: &str, player: &str) -> Request {
+        Request::Join {
+            room: String::from(room),
+            player: String::from(player),
+        }
+    }
+
+    #[test]
+    fn test_room() {
+        let words = WordList::from(["CRANE"]);
+        let alphabet: Vec<char> = ('A'..='Z').collect();
+        let server = Server::new(&words, &alphabet, 5, 2, StdRng::seed_from_u64(0));
+        let (mut alice, alice_output) = connection(&server);
+        let (mut bob, bob_output) = connection(&server);
+
+        // Test case 1: Players join the same room
+        assert_eq!(server.handle(&mut alice, join("lobby", "alice")), None);
+        assert_eq!(server.handle(&mut bob, join("lobby", "bob")), None);
+        assert!(matches!(
+            server.handle(&mut bob, join("lobby", "alice")),
+            Some(Response::Error {
+                code: ErrorCode::NameTaken,
+                ..
+            })
+        ));
+        assert_eq!(
+            received(&alice_output),
+            [
+                Response::Joined {
+                    room: String::from("lobby"),
+                    length: 5,
+                    max_attempts: 2
+                },
+                Response::PlayerJoined {
+                    player: String::from("bob")
+                },
+            ]
+        );
+        assert!(!server.rooms()["lobby"].is_over());
+        received(&bob_output);
+
+        // Test case 2: Each player is told when another finishes
+        assert!(matches!(
+            server.handle(
+                &mut alice,
+                Request::Guess {
+                    word: String::from("crate")
+                }
+            ),
+            Some(Response::Feedback { .. })
+        ));
+        assert_eq!(
+            server.handle(
+                &mut bob,
+                Request::Guess {
+                    word: String::from("crane")
+                }
+            ),
+            None
+        );
+        let finished = Response::PlayerFinished {
+            player: String::from("bob"),
+            status: GameStatus::Won,
+            attempts: 1,
+            time_ms: 0,
+        };
+        assert_eq!(received(&alice_output), vec![finished.clone()]);
+        assert_eq!(received(&bob_output)[1], finished);
+
+        // Test case 3: The word is revealed once every player finished
+        server.leave(&mut alice);
+        assert_eq!(
+            received(&bob_output),
+            vec![
+                Response::PlayerFinished {
+                    player: String::from("alice"),
+                    status: GameStatus::Abandoned,
+                    attempts: 1,
+                    time_ms: 0
+                },
+                Response::RoundOver {
+                    word: String::from("CRANE"),
+                    winner: None
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,338 @@
+use crate::game_logic::{analyze_guess, CharacterState};
+use crate::messages::{message, MessageId};
+use crate::word_source::WordSource;
+use core::fmt;
+use std::collections::{HashMap, HashSet};
+
+/// A guessed word along with the feedback received for each of its characters.
+pub type FeedbackRow = (String, Vec<CharacterState>);
+
+/// Represents the possible contradictions found between feedback rows.
+#[derive(Debug, PartialEq)]
+pub enum InconsistencyError {
+    /// The row does not have the same length as the previous ones. (expected length, given length)
+    UnexpectedLength(usize, usize),
+    /// Two different characters are marked as `CharacterState::Good` at the same position. (position, known character, new character)
+    ConflictingGood(usize, char, char),
+    /// A character is marked as `CharacterState::Good` at a position where it was previously excluded, or the reverse. (position, character)
+    ExcludedGood(usize, char),
+    /// The number of occurrences of a character cannot be satisfied. (character)
+    ImpossibleCount(char),
+    /// More characters are required than the word can hold. (required characters, word length)
+    TooManyCharacters(usize, usize),
+    /// The row contains a `CharacterState::NotTried` character.
+    NotTried,
+}
+
+impl std::error::Error for InconsistencyError {}
+
+impl fmt::Display for InconsistencyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            InconsistencyError::UnexpectedLength(expected, given) => message(
+                MessageId::SolverUnexpectedLength,
+                &[("expected", expected), ("given", given)],
+            ),
+            InconsistencyError::ConflictingGood(position, known, new) => message(
+                MessageId::SolverConflictingGood,
+                &[
+                    ("position", &(position + 1)),
+                    ("known", known),
+                    ("new", new),
+                ],
+            ),
+            InconsistencyError::ExcludedGood(position, character) => message(
+                MessageId::SolverExcludedGood,
+                &[("character", character), ("position", &(position + 1))],
+            ),
+            InconsistencyError::ImpossibleCount(character) => message(
+                MessageId::SolverImpossibleCount,
+                &[("character", character)],
+            ),
+            InconsistencyError::TooManyCharacters(required, length) => message(
+                MessageId::SolverTooManyCharacters,
+                &[("required", required), ("length", length)],
+            ),
+            InconsistencyError::NotTried => message(MessageId::SolverNotTried, &[]),
+        };
+        write!(f, "{text}")
+    }
+}
+
+/// Knowledge accumulated from the feedback rows of a game.
+///
+/// Each row is checked against the previous ones before being recorded,
+/// so that contradictory feedback is reported instead of silently leaving no candidate.
+#[derive(Debug)]
+pub struct Constraints {
+    word_length: usize,
+    rows: Vec<FeedbackRow>,
+    good: Vec<Option<char>>,
+    excluded: Vec<HashSet<char>>,
+    min_counts: HashMap<char, usize>,
+    exact_counts: HashMap<char, usize>,
+}
+
+impl Constraints {
+    /// Creates empty constraints for words of `word_length` characters.
+    #[must_use]
+    pub fn new(word_length: usize) -> Self {
+        Constraints {
+            word_length,
+            rows: Vec::new(),
+            good: vec![None; word_length],
+            excluded: vec![HashSet::new(); word_length],
+            min_counts: HashMap::new(),
+            exact_counts: HashMap::new(),
+        }
+    }
+
+    /// Returns the feedback rows recorded so far.
+    #[must_use]
+    pub fn rows(&self) -> &[FeedbackRow] {
+        &self.rows
+    }
+
+    /// Checks `feedback` against the previous rows and records it.
+    ///
+    /// # Arguments
+    ///
+    /// * `guess` - The guessed word, uppercased.
+    /// * `feedback` - The feedback received for each character of the `guess`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `InconsistencyError` if the row contradicts itself or earlier rows.
+    /// The constraints are left untouched in that case.
+    pub fn add_row(
+        &mut self,
+        guess: &str,
+        feedback: &[CharacterState],
+    ) -> Result<(), InconsistencyError> {
+        if feedback.len() != self.word_length {
+            return Err(InconsistencyError::UnexpectedLength(
+                self.word_length,
+                feedback.len(),
+            ));
+        }
+        if guess.chars().count() != self.word_length {
+            return Err(InconsistencyError::UnexpectedLength(
+                self.word_length,
+                guess.chars().count(),
+            ));
+        }
+
+        let mut good = self.good.clone();
+        let mut excluded = self.excluded.clone();
+        let mut min_counts = self.min_counts.clone();
+        let mut exact_counts = self.exact_counts.clone();
+
+        let mut row_present: HashMap<char, usize> = HashMap::new();
+        let mut row_absent: HashSet<char> = HashSet::new();
+        for (i, state) in feedback.iter().enumerate() {
+            match *state {
+                CharacterState::NotTried => return Err(InconsistencyError::NotTried),
+                CharacterState::Good(character) => {
+                    *row_present.entry(character).or_insert(0) += 1;
+                    good[i] = Some(character);
+                }
+                CharacterState::Misplaced(character) => {
+                    *row_present.entry(character).or_insert(0) += 1;
+                    excluded[i].insert(character);
+                }
+                CharacterState::NotPresent(character) => {
+                    row_absent.insert(character);
+                    excluded[i].insert(character);
+                }
+            }
+        }
+
+        for (i, state) in feedback.iter().enumerate() {
+            if let CharacterState::Good(character) = *state {
+                if let Some(known) = self.good[i] {
+                    if known != character {
+                        return Err(InconsistencyError::ConflictingGood(i, known, character));
+                    }
+                }
+            }
+        }
+        for (i, (known, excluded_chars)) in good.iter().zip(&excluded).enumerate() {
+            if let Some(known) = known {
+                if excluded_chars.contains(known) {
+                    return Err(InconsistencyError::ExcludedGood(i, *known));
+                }
+            }
+        }
+        for (&character, &count) in &row_present {
+            let min_count = min_counts.entry(character).or_insert(0);
+            *min_count = (*min_count).max(count);
+        }
+        for &character in &row_absent {
+            let count = row_present.get(&character).copied().unwrap_or(0);
+            match exact_counts.get(&character) {
+                Some(&known) if known != count => {
+                    return Err(InconsistencyError::ImpossibleCount(character))
+                }
+                _ => {
+                    exact_counts.insert(character, count);
+                }
+            }
+        }
+        for (character, &exact) in &exact_counts {
+            if min_counts.get(character).copied().unwrap_or(0) > exact {
+                return Err(InconsistencyError::ImpossibleCount(*character));
+            }
+            let fixed = good.iter().filter(|c| **c == Some(*character)).count();
+            if fixed > exact {
+                return Err(InconsistencyError::ImpossibleCount(*character));
+            }
+        }
+        let required: usize = min_counts.values().sum();
+        if required > self.word_length {
+            return Err(InconsistencyError::TooManyCharacters(
+                required,
+                self.word_length,
+            ));
+        }
+
+        self.good = good;
+        self.excluded = excluded;
+        self.min_counts = min_counts;
+        self.exact_counts = exact_counts;
+        self.rows.push((guess.to_string(), feedback.to_vec()));
+        Ok(())
+    }
+
+    /// Checks whether `word` would have produced every recorded feedback row.
+    #[must_use]
+    pub fn matches(&self, word: &str) -> bool {
+        word.chars().count() == self.word_length
+            && self
+                .rows
+                .iter()
+                .all(|(guess, feedback)| analyze_guess(word, guess) == *feedback)
+    }
+}
+
+/// Returns the words of `words` that are consistent with the `constraints`, sorted alphabetically.
+#[must_use]
+pub fn filter_candidates<W: WordSource + ?Sized>(
+    words: &W,
+    constraints: &Constraints,
+) -> Vec<String> {
+    let mut candidates: Vec<String> = words
+        .words(constraints.word_length)
+        .filter(|(word, _)| constraints.matches(word))
+        .map(|(word, _)| word.into_owned())
+        .collect();
+    candidates.sort();
+    candidates
+}
+
+/// Suggests at most `n_suggestions` next guesses among `candidates`.
+///
+/// Each candidate is scored by the number of remaining candidates sharing each of its distinct characters,
+/// so that guesses testing the most common characters come first.
+///
+/// # Examples
+///
+/// ```
+/// use wordlers::solver::suggest_guesses;
+///
+/// let candidates = vec![String::from("CRANE"), String::from("CRATE"), String::from("FUZZY")];
+/// assert_eq!(suggest_guesses(&candidates, 1), vec![String::from("CRANE")]);
+/// ```
+#[must_use]
+pub fn suggest_guesses(candidates: &[String], n_suggestions: usize) -> Vec<String> {
+    let char_counts = candidates.iter().fold(HashMap::new(), |mut acc, word| {
+        for character in word.chars().collect::<HashSet<char>>() {
+            *acc.entry(character).or_insert(0) += 1;
+        }
+        acc
+    });
+
+    let mut scored: Vec<(usize, &String)> = candidates
+        .iter()
+        .map(|word| {
+            let score = word
+                .chars()
+                .collect::<HashSet<char>>()
+                .iter()
+                .map(|c| char_counts.get(c).copied().unwrap_or(0))
+                .sum();
+            (score, word)
+        })
+        .collect();
+    scored.sort_by(|(score_a, word_a), (score_b, word_b)| {
+        score_b.cmp(score_a).then_with(|| word_a.cmp(word_b))
+    });
+    scored
+        .into_iter()
+        .take(n_suggestions)
+        .map(|(_, word)| word.clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::word_source::WordList;
+
+    #[test]
+    fn test_constraints() {
+        let mut constraints = Constraints::new(5);
+
+        // Test case 1: Feedback computed by the game is always consistent
+        let feedback = analyze_guess("CRATE", "CRANE");
+        assert!(constraints.add_row("CRANE", &feedback).is_ok());
+        assert!(constraints.matches("CRATE"));
+        assert!(!constraints.matches("CRANE"));
+
+        // Test case 2: Conflicting correct characters
+        let feedback = vec![
+            CharacterState::Good('B'),
+            CharacterState::NotPresent('O'),
+            CharacterState::NotPresent('U'),
+            CharacterState::NotPresent('N'),
+            CharacterState::NotPresent('D'),
+        ];
+        assert_eq!(
+            constraints.add_row("BOUND", &feedback),
+            Err(InconsistencyError::ConflictingGood(0, 'C', 'B'))
+        );
+
+        // Test case 3: A character previously absent is now present
+        let feedback = vec![
+            CharacterState::Good('C'),
+            CharacterState::Good('R'),
+            CharacterState::Good('A'),
+            CharacterState::Misplaced('N'),
+            CharacterState::NotPresent('S'),
+        ];
+        assert_eq!(
+            constraints.add_row("CRANS", &feedback),
+            Err(InconsistencyError::ImpossibleCount('N'))
+        );
+
+        // Test case 4: Wrong length
+        assert_eq!(
+            constraints.add_row("CRA", &feedback[..3]),
+            Err(InconsistencyError::UnexpectedLength(5, 3))
+        );
+        assert_eq!(constraints.rows().len(), 1);
+    }
+
+    #[test]
+    fn test_filter_candidates() {
+        let words = WordList::from(["CRATE", "CRANE", "TRACE", "FUZZY"]);
+        let mut constraints = Constraints::new(5);
+        constraints
+            .add_row("CRANE", &analyze_guess("CRATE", "CRANE"))
+            .unwrap();
+
+        assert_eq!(
+            filter_candidates(&words, &constraints),
+            vec![String::from("CRATE")]
+        );
+    }
+}
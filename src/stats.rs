@@ -0,0 +1,536 @@
+use crate::game::{GameEvent, Observer, Outcome, RoundResult};
+use crate::messages::{message, MessageId};
+use crate::protocol::millis;
+use core::fmt;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// Custom error type for stats file errors.
+#[derive(Debug)]
+pub enum Error {
+    /// Error indicating that the stats file cannot be read or written. (path, error)
+    Io(String, io::Error),
+    /// Error indicating that the stats file is not valid. (path, error)
+    Malformed(String, serde_json::Error),
+}
+
+impl std::error::Error for Error {}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            Error::Io(path, err) => {
+                message(MessageId::ErrorFile, &[("path", path), ("error", err)])
+            }
+            Error::Malformed(path, err) => {
+                message(MessageId::StatsMalformed, &[("path", path), ("error", err)])
+            }
+        };
+        write!(f, "{text}")
+    }
+}
+
+/// A run of the survival mode, where words follow each other until the first one not found.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct SurvivalRun {
+    /// Number of words found.
+    pub words: usize,
+    /// Score: one point per word found, plus one per attempt left unused.
+    pub score: usize,
+}
+
+impl SurvivalRun {
+    /// Counts a word found with `unused_attempts` attempts left.
+    pub fn add_word(&mut self, unused_attempts: usize) {
+        self.words += 1;
+        self.score += 1 + unused_attempts;
+    }
+
+    /// Checks whether the run beats the `other`: a higher score, or as high with more words.
+    #[must_use]
+    pub fn beats(&self, other: &SurvivalRun) -> bool {
+        (self.score, self.words) > (other.score, other.words)
+    }
+}
+
+/// The ways of playing the game, told apart in the stats.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GameMode {
+    /// A single word.
+    Classic,
+    /// A single word, with a time limit per guess.
+    Timed,
+    /// As many words as possible in a limited time.
+    Blitz,
+    /// Words following each other until one is not found.
+    Survival,
+    /// Players taking turns to find the same word first.
+    Race,
+}
+
+impl GameMode {
+    /// Every mode, in the order they are listed to the user.
+    pub const ALL: [GameMode; 5] = [
+        GameMode::Classic,
+        GameMode::Timed,
+        GameMode::Blitz,
+        GameMode::Survival,
+        GameMode::Race,
+    ];
+
+    /// Returns the name of the mode, as typed on the command line and stored in the stats file.
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            GameMode::Classic => "classic",
+            GameMode::Timed => "timed",
+            GameMode::Blitz => "blitz",
+            GameMode::Survival => "survival",
+            GameMode::Race => "race",
+        }
+    }
+}
+
+impl Display for GameMode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for GameMode {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        GameMode::ALL
+            .into_iter()
+            .find(|mode| mode.name() == name)
+            .ok_or_else(|| {
+                let available: Vec<&str> = GameMode::ALL.iter().map(|mode| mode.name()).collect();
+                message(
+                    MessageId::StatsUnknownMode,
+                    &[("mode", &name), ("available", &available.join(", "))],
+                )
+            })
+    }
+}
+
+/// A round played, as remembered in the stats.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RoundRecord {
+    /// The mode the round was played in.
+    pub mode: GameMode,
+    /// Length of the word to guess, in characters.
+    pub word_length: usize,
+    /// Whether the round was played in hard mode.
+    pub hard_mode: bool,
+    /// How the round ended: an abandoned round counts as played and not won.
+    pub outcome: Outcome,
+    /// Number of guesses scored.
+    pub attempts: usize,
+    /// Number of characters revealed by hints.
+    pub hints: usize,
+    /// Total time taken by the attempts, in milliseconds.
+    pub time_ms: u64,
+    /// Score of the round, see `wordlers::game::RoundResult::score`.
+    pub score: usize,
+}
+
+impl From<(GameMode, &RoundResult)> for RoundRecord {
+    fn from((mode, result): (GameMode, &RoundResult)) -> Self {
+        RoundRecord {
+            mode,
+            word_length: result.word_length,
+            hard_mode: result.hard_mode,
+            outcome: result.outcome,
+            attempts: result.attempts,
+            hints: result.hints,
+            time_ms: millis(result.time),
+            score: result.score,
+        }
+    }
+}
+
+/// What is remembered of a player.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct PlayerStats {
+    /// Best run of the survival mode, if any was played.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub best_survival: Option<SurvivalRun>,
+    /// Rounds played, oldest first.
+    #[serde(default)]
+    pub rounds: Vec<RoundRecord>,
+}
+
+impl PlayerStats {
+    /// Records a survival `run`, kept if it beats the best one.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the run is the new best one.
+    pub fn record_survival(&mut self, run: SurvivalRun) -> bool {
+        let best = self
+            .best_survival
+            .is_none_or(|best_survival| run.beats(&best_survival));
+        if best {
+            self.best_survival = Some(run);
+        }
+        best
+    }
+}
+
+/// How a player ranks on the leaderboard, from the rounds matching its filters.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LeaderboardEntry {
+    /// Name of the player.
+    pub player: String,
+    /// Number of rounds played.
+    pub played: usize,
+    /// Share of the rounds won, from 0 to 1.
+    pub win_rate: f64,
+    /// Average number of guesses of the rounds won, or `None` if none was won.
+    pub average_guesses: Option<f64>,
+    /// Longest run of rounds won in a row.
+    pub best_streak: usize,
+}
+
+impl LeaderboardEntry {
+    /// Computes the entry of `player` from the `rounds` it played, oldest first.
+    ///
+    /// # Returns
+    ///
+    /// `None` if no round was played.
+    fn new<'r>(player: &str, rounds: impl Iterator<Item = &'r RoundRecord>) -> Option<Self> {
+        let mut played = 0;
+        let mut wins = 0;
+        let mut guesses = 0;
+        let mut streak = 0;
+        let mut best_streak = 0;
+        for round in rounds {
+            played += 1;
+            if round.outcome == Outcome::Won {
+                wins += 1;
+                guesses += round.attempts;
+                streak += 1;
+                best_streak = best_streak.max(streak);
+            } else {
+                streak = 0;
+            }
+        }
+        (played > 0).then(|| LeaderboardEntry {
+            player: String::from(player),
+            played,
+            win_rate: wins as f64 / played as f64,
+            average_guesses: (wins > 0).then(|| guesses as f64 / wins as f64),
+            best_streak,
+        })
+    }
+}
+
+/// What is remembered between games, for each player, stored as JSON in the stats file.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Stats {
+    /// The stats of each player, by name.
+    #[serde(default)]
+    pub players: BTreeMap<String, PlayerStats>,
+}
+
+impl Stats {
+    /// Loads the stats file at `path`; a missing file holds empty stats.
+    ///
+    /// # Errors
+    ///
+    /// - `wordlers::stats::Error::Io` if the file exists but cannot be read.
+    /// - `wordlers::stats::Error::Malformed` if the file is not valid.
+    pub fn load(path: &Path) -> Result<Self, Error> {
+        let content = match std::fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Stats::default()),
+            Err(err) => return Err(Error::Io(path.display().to_string(), err)),
+        };
+        serde_json::from_str(&content)
+            .map_err(|err| Error::Malformed(path.display().to_string(), err))
+    }
+
+    /// Saves the stats to the file at `path`, creating its directory if needed.
+    ///
+    /// # Errors
+    ///
+    /// `wordlers::stats::Error::Io` if the file cannot be written.
+    pub fn save(&self, path: &Path) -> Result<(), Error> {
+        let io_error = |err| Error::Io(path.display().to_string(), err);
+        if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir).map_err(io_error)?;
+        }
+        let content = serde_json::to_string_pretty(self)
+            .map_err(io::Error::from)
+            .map_err(io_error)?;
+        std::fs::write(path, content + "\n").map_err(io_error)
+    }
+
+    /// Returns the stats of the `player`, created empty if needed.
+    pub fn player(&mut self, player: &str) -> &mut PlayerStats {
+        self.players.entry(String::from(player)).or_default()
+    }
+
+    /// Ranks the players on the rounds of `word_length` characters played in `mode`, `None` matching any:
+    /// by win rate first, then by fewer guesses on average, then by best streak.
+    /// Players without such rounds are left out.
+    #[must_use]
+    pub fn leaderboard(
+        &self,
+        word_length: Option<usize>,
+        mode: Option<GameMode>,
+    ) -> Vec<LeaderboardEntry> {
+        let mut entries: Vec<LeaderboardEntry> = self
+            .players
+            .iter()
+            .filter_map(|(player, stats)| {
+                LeaderboardEntry::new(
+                    player,
+                    stats.rounds.iter().filter(|round| {
+                        word_length.is_none_or(|length| round.word_length == length)
+                            && mode.is_none_or(|mode| round.mode == mode)
+                    }),
+                )
+            })
+            .collect();
+        entries.sort_by(|a, b| {
+            b.win_rate
+                .total_cmp(&a.win_rate)
+                .then(
+                    a.average_guesses
+                        .unwrap_or(f64::INFINITY)
+                        .total_cmp(&b.average_guesses.unwrap_or(f64::INFINITY)),
+                )
+                .then(b.best_streak.cmp(&a.best_streak))
+        });
+        entries
+    }
+}
+
+/// Records the rounds it observes, played in the same mode, to add them to the stats of a player.
+/// Abandoned rounds are recorded as such, so that leaving a round does not spare a loss.
+#[derive(Debug)]
+pub struct StatsRecorder {
+    mode: GameMode,
+    hard_mode: bool,
+    word_length: usize,
+    max_attempts: usize,
+    attempts: usize,
+    timeouts: usize,
+    hints: usize,
+    records: Vec<RoundRecord>,
+}
+
+impl StatsRecorder {
+    /// Creates a recorder for rounds played in `mode`, in hard mode if `hard_mode` is set.
+    #[must_use]
+    pub fn new(mode: GameMode, hard_mode: bool) -> Self {
+        StatsRecorder {
+            mode,
+            hard_mode,
+            word_length: 0,
+            max_attempts: 0,
+            attempts: 0,
+            timeouts: 0,
+            hints: 0,
+            records: Vec::new(),
+        }
+    }
+
+    /// Returns the rounds recorded so far.
+    #[must_use]
+    pub fn records(&self) -> &[RoundRecord] {
+        &self.records
+    }
+}
+
+impl Observer for StatsRecorder {
+    fn notify(&mut self, event: &GameEvent) {
+        match event {
+            GameEvent::RoundStarted(word_length, max_attempts) => {
+                self.word_length = *word_length;
+                self.max_attempts = *max_attempts;
+                self.attempts = 0;
+                self.timeouts = 0;
+                self.hints = 0;
+            }
+            GameEvent::GuessScored(..) => self.attempts += 1,
+            GameEvent::GuessTimedOut(_) => self.timeouts += 1,
+            GameEvent::HintUsed(..) => self.hints += 1,
+            GameEvent::GuessRejected(..) => {}
+            GameEvent::RoundFinished(outcome, _, time) => {
+                let mut result = RoundResult {
+                    outcome: *outcome,
+                    word_length: self.word_length,
+                    attempts: self.attempts,
+                    unused_attempts: self
+                        .max_attempts
+                        .saturating_sub(self.attempts + self.timeouts),
+                    hints: self.hints,
+                    time: *time,
+                    hard_mode: self.hard_mode,
+                    score: 0,
+                };
+                result.score = result.score();
+                self.records.push(RoundRecord::from((self.mode, &result)));
+            }
+        }
+    }
+}
+
+/// Returns the default path of the stats file:
+/// `$WORDLERS_STATS_FILE` if set, otherwise `$HOME/.local/share/wordlers/stats.json`.
+#[must_use]
+pub fn default_stats_path() -> Option<PathBuf> {
+    if let Some(stats_file) = std::env::var_os("WORDLERS_STATS_FILE") {
+        return Some(PathBuf::from(stats_file));
+    }
+    std::env::var_os("HOME").map(|home| Path::new(&home).join(".local/share/wordlers/stats.json"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::Round;
+
+    #[test]
+    fn test_survival_run() {
+        // Test case 1: Unused attempts add to the score
+        let mut run = SurvivalRun::default();
+        run.add_word(3);
+        run.add_word(0);
+        assert_eq!(run, SurvivalRun { words: 2, score: 5 });
+
+        // Test case 2: Only better runs are kept as the best one
+        let mut stats = PlayerStats::default();
+        assert!(stats.record_survival(run));
+        assert!(!stats.record_survival(SurvivalRun { words: 4, score: 4 }));
+        assert!(!stats.record_survival(run));
+        assert!(stats.record_survival(SurvivalRun { words: 3, score: 5 }));
+        assert_eq!(
+            stats.best_survival,
+            Some(SurvivalRun { words: 3, score: 5 })
+        );
+    }
+
+    #[test]
+    fn test_stats_recorder() {
+        let alphabet: Vec<char> = ('A'..='Z').collect();
+        let mut recorder = StatsRecorder::new(GameMode::Blitz, true);
+
+        // Test case 1: Finished rounds are recorded with their score
+        let mut round = Round::new("CRANE", &alphabet, 6).with_hard_mode(true);
+        round.add_observer(&mut recorder);
+        round.start();
+        round.guess("TRACE");
+        round.hint();
+        round.guess("CRANE");
+        let result = round.result().unwrap();
+        drop(round);
+        assert_eq!(
+            recorder.records(),
+            [RoundRecord::from((GameMode::Blitz, &result))]
+        );
+        assert_eq!(recorder.records()[0].hints, 1);
+
+        // Test case 2: Abandoned rounds are recorded as such, without score
+        let mut round = Round::new("CRANE", &alphabet, 6);
+        round.add_observer(&mut recorder);
+        round.start();
+        round.guess("TRACE");
+        round.abandon();
+        drop(round);
+        assert_eq!(recorder.records().len(), 2);
+        assert_eq!(recorder.records()[1].outcome, Outcome::Abandoned);
+        assert_eq!(recorder.records()[1].attempts, 1);
+        assert_eq!(recorder.records()[1].score, 0);
+    }
+
+    #[test]
+    fn test_leaderboard() {
+        let round = |mode, word_length, outcome, attempts| RoundRecord {
+            mode,
+            word_length,
+            hard_mode: false,
+            outcome,
+            attempts,
+            hints: 0,
+            time_ms: 0,
+            score: 0,
+        };
+        let mut stats = Stats::default();
+        stats.player("alice").rounds = vec![
+            round(GameMode::Classic, 5, Outcome::Won, 4),
+            round(GameMode::Classic, 5, Outcome::Lost, 5),
+            round(GameMode::Classic, 6, Outcome::Won, 3),
+            round(GameMode::Classic, 6, Outcome::Won, 3),
+        ];
+        stats.player("bob").rounds = vec![
+            round(GameMode::Classic, 5, Outcome::Won, 3),
+            round(GameMode::Classic, 5, Outcome::Abandoned, 1),
+            round(GameMode::Blitz, 5, Outcome::Won, 2),
+        ];
+        stats.player("carol").rounds = vec![round(GameMode::Blitz, 5, Outcome::Lost, 5)];
+
+        // Test case 1: Ranked by win rate first
+        let entries = stats.leaderboard(None, None);
+        let players: Vec<&str> = entries.iter().map(|entry| entry.player.as_str()).collect();
+        assert_eq!(players, ["alice", "bob", "carol"]);
+        assert_eq!(entries[0].played, 4);
+        assert_eq!(entries[0].win_rate, 0.75);
+        assert_eq!(entries[0].average_guesses, Some(10.0 / 3.0));
+        assert_eq!(entries[0].best_streak, 2);
+        assert_eq!(entries[2].average_guesses, None);
+
+        // Test case 2: Abandoned rounds count as played and not won
+        assert_eq!(entries[1].played, 3);
+        assert_eq!(entries[1].win_rate, 2.0 / 3.0);
+        assert_eq!(entries[1].best_streak, 1);
+
+        // Test case 3: Then by fewer guesses, filtering by length
+        let entries = stats.leaderboard(Some(5), Some(GameMode::Classic));
+        let players: Vec<&str> = entries.iter().map(|entry| entry.player.as_str()).collect();
+        assert_eq!(players, ["bob", "alice"]);
+        assert_eq!(entries[0].win_rate, 0.5);
+
+        // Test case 4: Players without matching rounds are left out
+        let entries = stats.leaderboard(None, Some(GameMode::Blitz));
+        let players: Vec<&str> = entries.iter().map(|entry| entry.player.as_str()).collect();
+        assert_eq!(players, ["bob", "carol"]);
+        assert!(stats.leaderboard(Some(7), None).is_empty());
+    }
+
+    #[test]
+    fn test_game_mode() {
+        assert_eq!("survival".parse::<GameMode>(), Ok(GameMode::Survival));
+        assert!("zen".parse::<GameMode>().is_err());
+        assert_eq!(GameMode::Race.to_string(), "race");
+    }
+
+    #[test]
+    fn test_load_save() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("wordlers").join("stats.json");
+
+        // Test case 1: A missing file holds empty stats
+        assert_eq!(Stats::load(&path).unwrap(), Stats::default());
+
+        // Test case 2: Saved stats are loaded back, the directory being created
+        let mut stats = Stats::default();
+        stats
+            .player("alice")
+            .record_survival(SurvivalRun { words: 2, score: 9 });
+        stats.save(&path).unwrap();
+        assert_eq!(Stats::load(&path).unwrap(), stats);
+
+        // Test case 3: A malformed file is reported
+        std::fs::write(&path, "{ best").unwrap();
+        assert!(matches!(Stats::load(&path), Err(Error::Malformed(..))));
+    }
+}
@@ -0,0 +1,190 @@
+use std::cell::Cell;
+use std::time::{Duration, Instant};
+
+/// A source of the current time, so that timed games can be tested without waiting.
+pub trait Clock {
+    /// Returns the current time.
+    fn now(&self) -> Instant;
+}
+
+/// The clock of the system.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// A clock that only moves when told to, standing for the system clock in tests.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use wordlers::timer::{Clock, ManualClock};
+///
+/// let clock = ManualClock::new();
+/// let start = clock.now();
+/// clock.advance(Duration::from_secs(3));
+/// assert_eq!(clock.now() - start, Duration::from_secs(3));
+/// ```
+#[derive(Debug)]
+pub struct ManualClock {
+    now: Cell<Instant>,
+}
+
+impl ManualClock {
+    /// Creates a clock stopped at the current time.
+    #[must_use]
+    pub fn new() -> Self {
+        ManualClock {
+            now: Cell::new(Instant::now()),
+        }
+    }
+
+    /// Moves the clock forward by `duration`.
+    pub fn advance(&self, duration: Duration) {
+        self.now.set(self.now.get() + duration);
+    }
+}
+
+impl Default for ManualClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> Instant {
+        self.now.get()
+    }
+}
+
+/// Which time limit of a `Timer` is over.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Expiry {
+    /// The current guess took too long.
+    Guess,
+    /// The whole run is over.
+    Run,
+}
+
+/// Counts down the time left for the current guess and for a whole run of rounds, as read on a `Clock`.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use wordlers::timer::{Expiry, ManualClock, Timer};
+///
+/// let clock = ManualClock::new();
+/// let mut timer = Timer::new(&clock, Some(Duration::from_secs(10)), Some(Duration::from_secs(60)));
+/// timer.start_guess();
+/// clock.advance(Duration::from_secs(4));
+/// assert_eq!(timer.remaining(), Some(Duration::from_secs(6)));
+/// clock.advance(Duration::from_secs(6));
+/// assert_eq!(timer.expired(), Some(Expiry::Guess));
+/// ```
+pub struct Timer<'c> {
+    clock: &'c dyn Clock,
+    per_guess: Option<Duration>,
+    guess_deadline: Option<Instant>,
+    run_deadline: Option<Instant>,
+}
+
+impl<'c> Timer<'c> {
+    /// Creates a timer reading the `clock`, giving `per_guess` to each guess and `total` to the run,
+    /// which starts now. `None` means no limit.
+    #[must_use]
+    pub fn new(clock: &'c dyn Clock, per_guess: Option<Duration>, total: Option<Duration>) -> Self {
+        Timer {
+            clock,
+            per_guess,
+            guess_deadline: None,
+            run_deadline: total.map(|total| clock.now() + total),
+        }
+    }
+
+    /// Returns the clock read by the timer.
+    #[must_use]
+    pub fn clock(&self) -> &'c dyn Clock {
+        self.clock
+    }
+
+    /// Checks whether the run itself is limited in time.
+    #[must_use]
+    pub fn is_run_limited(&self) -> bool {
+        self.run_deadline.is_some()
+    }
+
+    /// Starts the countdown of the next guess.
+    pub fn start_guess(&mut self) {
+        self.guess_deadline = self.per_guess.map(|limit| self.clock.now() + limit);
+    }
+
+    /// Returns the time left before the closest deadline, or `None` if there is no limit.
+    #[must_use]
+    pub fn remaining(&self) -> Option<Duration> {
+        let now = self.clock.now();
+        [self.guess_deadline, self.run_deadline]
+            .into_iter()
+            .flatten()
+            .map(|deadline| deadline.saturating_duration_since(now))
+            .min()
+    }
+
+    /// Returns which time limit is over, the run's first, or `None` if there is time left.
+    #[must_use]
+    pub fn expired(&self) -> Option<Expiry> {
+        let now = self.clock.now();
+        if self.run_deadline.is_some_and(|deadline| now >= deadline) {
+            Some(Expiry::Run)
+        } else if self.guess_deadline.is_some_and(|deadline| now >= deadline) {
+            Some(Expiry::Guess)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timer() {
+        let clock = ManualClock::new();
+        let seconds = Duration::from_secs;
+
+        // Test case 1: Without limits, time never runs out
+        let mut timer = Timer::new(&clock, None, None);
+        timer.start_guess();
+        clock.advance(seconds(1_000));
+        assert_eq!(timer.remaining(), None);
+        assert_eq!(timer.expired(), None);
+        assert!(!timer.is_run_limited());
+
+        // Test case 2: Each guess gets its own countdown
+        let mut timer = Timer::new(&clock, Some(seconds(10)), None);
+        timer.start_guess();
+        clock.advance(seconds(7));
+        assert_eq!(timer.remaining(), Some(seconds(3)));
+        timer.start_guess();
+        clock.advance(seconds(7));
+        assert_eq!(timer.expired(), None);
+        clock.advance(seconds(5));
+        assert_eq!(timer.remaining(), Some(Duration::ZERO));
+        assert_eq!(timer.expired(), Some(Expiry::Guess));
+
+        // Test case 3: The run ends at its deadline, whatever the guess countdown
+        let mut timer = Timer::new(&clock, Some(seconds(10)), Some(seconds(15)));
+        assert!(timer.is_run_limited());
+        clock.advance(seconds(8));
+        timer.start_guess();
+        assert_eq!(timer.remaining(), Some(seconds(7)));
+        clock.advance(seconds(7));
+        assert_eq!(timer.expired(), Some(Expiry::Run));
+    }
+}
@@ -0,0 +1,533 @@
+use crate::game::{GameEvent, Observer, Round};
+use crate::game_logic::{CharacterState, FeedbackPattern};
+use crate::messages::{message, MessageId};
+use crate::protocol::{millis, write_json_line, GameStatus};
+use crate::stats::GameMode;
+use crate::timer::ManualClock;
+use crate::user_input::format_and_check::UserInputError;
+use core::fmt;
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use std::io::{self, BufRead, Write};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Custom error type for transcript file errors.
+#[derive(Debug)]
+pub enum Error {
+    /// Error indicating that the transcript cannot be read. (path, error)
+    Io(String, io::Error),
+    /// Error indicating that a line of the transcript is not valid. (path, line number, error)
+    Malformed(String, usize, serde_json::Error),
+    /// Error indicating that a step of the transcript comes before any round. (path, line number)
+    MissingRound(String, usize),
+    /// Error indicating that steps of the transcript do not match the rules. (number of steps)
+    Tampered(usize),
+}
+
+impl std::error::Error for Error {}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            Error::Io(path, err) => {
+                message(MessageId::ErrorFile, &[("path", path), ("error", err)])
+            }
+            Error::Malformed(path, line, err) => message(
+                MessageId::TranscriptMalformed,
+                &[("path", path), ("line", line), ("error", err)],
+            ),
+            Error::MissingRound(path, line) => message(
+                MessageId::TranscriptMissingRound,
+                &[("path", path), ("line", line)],
+            ),
+            Error::Tampered(count) => message(MessageId::TranscriptTampered, &[("count", count)]),
+        };
+        write!(f, "{text}")
+    }
+}
+
+/// How a round was set up, apart from its word.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RoundSettings {
+    /// Name of the player.
+    pub player: String,
+    /// Mode of the game the round belongs to.
+    pub mode: GameMode,
+    /// Whether the round is played in hard mode.
+    pub hard_mode: bool,
+    /// Seed of the random word choice, if one was given.
+    pub seed: Option<u64>,
+    /// Code of the language pack.
+    pub lang: String,
+}
+
+/// A line of a transcript, written as one JSON object, e.g. `{"type": "hint", "position": 0, "character": "C"}`.
+///
+/// A round is written as a `Round` line, followed by a line per step, and a `Finish` line.
+/// Times are counted in milliseconds from the start of the round.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Entry {
+    /// A round starts, with its `word`, and the Unix time it `started_at`, in seconds.
+    Round {
+        word: String,
+        max_attempts: usize,
+        settings: RoundSettings,
+        started_at: u64,
+    },
+    /// A `guess` was scored.
+    Guess {
+        guess: String,
+        feedback: Vec<CharacterState>,
+        elapsed_ms: u64,
+    },
+    /// The `character` at `position` was revealed.
+    Hint { position: usize, character: char },
+    /// An attempt ran out of time.
+    TimedOut { elapsed_ms: u64 },
+    /// The round ended.
+    Finish { status: GameStatus, elapsed_ms: u64 },
+}
+
+/// Writes each round it observes to a transcript, once the round is over.
+///
+/// The lines of a round are written at once, so that the rounds of several recorders sharing a file
+/// opened in append mode do not mix.
+///
+/// # Examples
+///
+/// ```
+/// use wordlers::game::Round;
+/// use wordlers::stats::GameMode;
+/// use wordlers::transcript::{read_transcript, RoundSettings, TranscriptRecorder};
+///
+/// let settings = RoundSettings {
+///     player: String::from("ada"),
+///     mode: GameMode::Classic,
+///     hard_mode: false,
+///     seed: Some(7),
+///     lang: String::from("en"),
+/// };
+/// let alphabet: Vec<char> = ('A'..='Z').collect();
+/// let mut recorder = TranscriptRecorder::new(Vec::new(), settings.clone());
+/// let mut round = Round::new("CRANE", &alphabet, 6);
+/// round.add_observer(&mut recorder);
+/// round.start();
+/// round.guess("crate");
+/// round.guess("crane");
+/// drop(round);
+///
+/// let output = recorder.finish().unwrap();
+/// let rounds = read_transcript(output.as_slice(), "transcript.jsonl").unwrap();
+/// assert_eq!(rounds[0].word, "CRANE");
+/// assert_eq!(rounds[0].settings, settings);
+/// assert_eq!(rounds[0].steps.len(), 3);
+/// ```
+pub struct TranscriptRecorder<W: Write> {
+    output: W,
+    settings: RoundSettings,
+    max_attempts: usize,
+    started_at: u64,
+    elapsed: Duration,
+    steps: Vec<Entry>,
+    error: Option<io::Error>,
+}
+
+impl<W: Write> TranscriptRecorder<W> {
+    /// Creates a recorder writing to `output` the rounds set up with the `settings`.
+    #[must_use]
+    pub fn new(output: W, settings: RoundSettings) -> Self {
+        TranscriptRecorder {
+            output,
+            settings,
+            max_attempts: 0,
+            started_at: 0,
+            elapsed: Duration::ZERO,
+            steps: Vec::new(),
+            error: None,
+        }
+    }
+
+    /// Stops recording.
+    ///
+    /// # Returns
+    ///
+    /// The output the rounds were written to.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error met while writing a round.
+    pub fn finish(self) -> io::Result<W> {
+        match self.error {
+            Some(err) => Err(err),
+            None => Ok(self.output),
+        }
+    }
+
+    /// Writes the round that just ended, as a `Round` line, its steps and the `Finish` line.
+    fn write_round(&mut self, word: &str, finish: Entry) -> io::Result<()> {
+        let round = Entry::Round {
+            word: String::from(word),
+            max_attempts: self.max_attempts,
+            settings: self.settings.clone(),
+            started_at: self.started_at,
+        };
+        let mut lines = Vec::new();
+        for entry in [&round].into_iter().chain(&self.steps).chain([&finish]) {
+            write_json_line(&mut lines, entry)?;
+        }
+        self.output.write_all(&lines)?;
+        self.output.flush()
+    }
+}
+
+impl<W: Write> Observer for TranscriptRecorder<W> {
+    fn notify(&mut self, event: &GameEvent) {
+        match event {
+            GameEvent::RoundStarted(_, max_attempts) => {
+                self.max_attempts = *max_attempts;
+                self.started_at = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map_or(0, |since_epoch| since_epoch.as_secs());
+                self.elapsed = Duration::ZERO;
+                self.steps.clear();
+            }
+            GameEvent::GuessScored(guess, feedback, time) => {
+                self.elapsed += *time;
+                self.steps.push(Entry::Guess {
+                    guess: guess.clone(),
+                    feedback: feedback.clone(),
+                    elapsed_ms: millis(self.elapsed),
+                });
+            }
+            GameEvent::HintUsed(position, character) => self.steps.push(Entry::Hint {
+                position: *position,
+                character: *character,
+            }),
+            GameEvent::GuessTimedOut(time) => {
+                self.elapsed += *time;
+                self.steps.push(Entry::TimedOut {
+                    elapsed_ms: millis(self.elapsed),
+                });
+            }
+            GameEvent::RoundFinished(outcome, word, elapsed) => {
+                let finish = Entry::Finish {
+                    status: GameStatus::from(Some(*outcome)),
+                    elapsed_ms: millis(*elapsed),
+                };
+                if let Err(err) = self.write_round(word, finish) {
+                    self.error.get_or_insert(err);
+                }
+            }
+            GameEvent::GuessRejected(..) => {}
+        }
+    }
+}
+
+/// A difference between a transcript and the rules of the game.
+#[derive(Debug)]
+pub enum Mismatch {
+    /// The stored feedback of a guess differs from the one the rules give. (guess, stored, computed)
+    Feedback(String, Vec<CharacterState>, Vec<CharacterState>),
+    /// A stored guess is not a valid guess. (guess, reason)
+    Rejected(String, UserInputError),
+    /// A stored guess comes after the end of the round. (guess)
+    AfterEnd(String),
+    /// A stored hint differs from the one the rules give. (position, character)
+    Hint(usize, char),
+    /// The stored end of the round differs from the one the rules give. (stored, computed)
+    Status(GameStatus, GameStatus),
+}
+
+/// Returns the feedback as a pattern such as `GY..G`, or as it is stored if it cannot be one.
+fn format_feedback(feedback: &[CharacterState]) -> String {
+    FeedbackPattern::from_states(feedback)
+        .map_or_else(|_| format!("{feedback:?}"), |pattern| pattern.to_string())
+}
+
+/// Returns the name of a `status`, as in the transcript.
+fn status_name(status: GameStatus) -> &'static str {
+    match status {
+        GameStatus::InProgress => "in_progress",
+        GameStatus::Won => "won",
+        GameStatus::Lost => "lost",
+        GameStatus::Abandoned => "abandoned",
+    }
+}
+
+impl Display for Mismatch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            Mismatch::Feedback(guess, stored, computed) => message(
+                MessageId::TranscriptFeedbackMismatch,
+                &[
+                    ("guess", guess),
+                    ("stored", &format_feedback(stored)),
+                    ("computed", &format_feedback(computed)),
+                ],
+            ),
+            Mismatch::Rejected(guess, err) => message(
+                MessageId::TranscriptRejected,
+                &[("guess", guess), ("error", err)],
+            ),
+            Mismatch::AfterEnd(guess) => {
+                message(MessageId::TranscriptAfterEnd, &[("guess", guess)])
+            }
+            Mismatch::Hint(position, character) => message(
+                MessageId::TranscriptHintMismatch,
+                &[("character", character), ("position", &(position + 1))],
+            ),
+            Mismatch::Status(stored, computed) => message(
+                MessageId::TranscriptStatusMismatch,
+                &[
+                    ("stored", &status_name(*stored)),
+                    ("computed", &status_name(*computed)),
+                ],
+            ),
+        };
+        write!(f, "{text}")
+    }
+}
+
+/// A round read from a transcript.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TranscriptRound {
+    /// The word to guess.
+    pub word: String,
+    /// The maximum number of attempts.
+    pub max_attempts: usize,
+    /// How the round was set up.
+    pub settings: RoundSettings,
+    /// The Unix time the round started at, in seconds.
+    pub started_at: u64,
+    /// The guesses, hints, time-outs and end of the round, in the order they happened.
+    pub steps: Vec<Entry>,
+}
+
+impl TranscriptRound {
+    /// Plays the round again with the stored steps, notifying the `observers` as in the original game,
+    /// and checks each step against the rules, with the characters of `alphabet`.
+    ///
+    /// The round is timed with the stored times. The board shows the feedback given by the rules,
+    /// and a round without a `Finish` step is abandoned after the last one.
+    ///
+    /// # Returns
+    ///
+    /// The steps that do not match the rules, e.g. because the transcript was edited or the rules changed.
+    pub fn replay(&self, alphabet: &[char], observers: Vec<&mut dyn Observer>) -> Vec<Mismatch> {
+        let clock = ManualClock::new();
+        let mut elapsed_ms = 0;
+        let mut advance_to = |time_ms: u64| {
+            clock.advance(Duration::from_millis(time_ms.saturating_sub(elapsed_ms)));
+            elapsed_ms = elapsed_ms.max(time_ms);
+        };
+        let mut round = Round::new(&self.word, alphabet, self.max_attempts)
+            .with_hard_mode(self.settings.hard_mode)
+            .with_clock(&clock);
+        for observer in observers {
+            round.add_observer(observer);
+        }
+        round.start();
+
+        let mut mismatches = Vec::new();
+        for step in &self.steps {
+            match step {
+                Entry::Guess {
+                    guess,
+                    feedback,
+                    elapsed_ms,
+                } => {
+                    if round.is_finished() {
+                        mismatches.push(Mismatch::AfterEnd(guess.clone()));
+                        continue;
+                    }
+                    if let Err(err) = round.check(guess) {
+                        mismatches.push(Mismatch::Rejected(guess.clone(), err));
+                        continue;
+                    }
+                    advance_to(*elapsed_ms);
+                    let computed = round.guess(guess).unwrap_or_default();
+                    if computed != *feedback {
+                        mismatches.push(Mismatch::Feedback(
+                            guess.clone(),
+                            feedback.clone(),
+                            computed,
+                        ));
+                    }
+                }
+                Entry::Hint {
+                    position,
+                    character,
+                } => {
+                    if round.hint() != Some((*position, *character)) {
+                        mismatches.push(Mismatch::Hint(*position, *character));
+                    }
+                }
+                Entry::TimedOut { elapsed_ms } => {
+                    advance_to(*elapsed_ms);
+                    round.time_out();
+                }
+                Entry::Finish { status, elapsed_ms } => {
+                    advance_to(*elapsed_ms);
+                    let computed = GameStatus::from(round.outcome());
+                    if *status != computed
+                        && !(*status == GameStatus::Abandoned && !round.is_finished())
+                    {
+                        mismatches.push(Mismatch::Status(*status, computed));
+                    }
+                    round.abandon();
+                }
+                Entry::Round { .. } => {}
+            }
+        }
+        round.abandon();
+        mismatches
+    }
+}
+
+/// Reads the rounds of a transcript, written by `TranscriptRecorder`. Blank lines are skipped.
+///
+/// # Arguments
+///
+/// * `reader` - The transcript.
+/// * `path` - The path of the transcript, to report errors.
+///
+/// # Errors
+///
+/// Returns an error if the transcript cannot be read, if a line is not valid,
+/// or if a step comes before the first round.
+pub fn read_transcript(reader: impl BufRead, path: &str) -> Result<Vec<TranscriptRound>, Error> {
+    let mut rounds: Vec<TranscriptRound> = Vec::new();
+    for (i, line) in reader.lines().enumerate() {
+        let line = line.map_err(|err| Error::Io(String::from(path), err))?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let entry = serde_json::from_str::<Entry>(&line)
+            .map_err(|err| Error::Malformed(String::from(path), i + 1, err))?;
+        match entry {
+            Entry::Round {
+                word,
+                max_attempts,
+                settings,
+                started_at,
+            } => rounds.push(TranscriptRound {
+                word,
+                max_attempts,
+                settings,
+                started_at,
+                steps: Vec::new(),
+            }),
+            step => rounds
+                .last_mut()
+                .ok_or_else(|| Error::MissingRound(String::from(path), i + 1))?
+                .steps
+                .push(step),
+        }
+    }
+    Ok(rounds)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settings(hard_mode: bool) -> RoundSettings {
+        RoundSettings {
+            player: String::from("ada"),
+            mode: GameMode::Classic,
+            hard_mode,
+            seed: None,
+            lang: String::from("en"),
+        }
+    }
+
+    fn record(word: &str, hard_mode: bool, play: impl FnOnce(&mut Round<'_>)) -> Vec<u8> {
+        let alphabet: Vec<char> = ('A'..='Z').collect();
+        let clock = ManualClock::new();
+        let mut recorder = TranscriptRecorder::new(Vec::new(), settings(hard_mode));
+        let mut round = Round::new(word, &alphabet, 3)
+            .with_hard_mode(hard_mode)
+            .with_clock(&clock);
+        round.add_observer(&mut recorder);
+        round.start();
+        clock.advance(Duration::from_millis(1_500));
+        play(&mut round);
+        drop(round);
+        recorder.finish().unwrap()
+    }
+
+    #[test]
+    fn test_transcript() {
+        let alphabet: Vec<char> = ('A'..='Z').collect();
+
+        // Test case 1: A round is written as its settings, steps and end, and replays without mismatch
+        let output = record("CRANE", false, |round| {
+            round.guess("crate");
+            round.hint();
+            round.guess("crane");
+        });
+        let text = String::from_utf8(output.clone()).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 5);
+        assert!(lines[0].starts_with(r#"{"type":"round","word":"CRANE","max_attempts":3,"#));
+        assert!(lines[1].ends_with(r#""elapsed_ms":1500}"#));
+        assert_eq!(lines[2], r#"{"type":"hint","position":3,"character":"N"}"#);
+        assert_eq!(
+            lines[4],
+            r#"{"type":"finish","status":"won","elapsed_ms":1500}"#
+        );
+        let rounds = read_transcript(output.as_slice(), "t.jsonl").unwrap();
+        assert_eq!(rounds.len(), 1);
+        assert!(rounds[0].replay(&alphabet, Vec::new()).is_empty());
+
+        // Test case 2: An edited feedback is detected
+        let edited = text.replacen(
+            r#""state":"not_present","character":"T""#,
+            r#""state":"good","character":"T""#,
+            1,
+        );
+        let rounds = read_transcript(edited.as_bytes(), "t.jsonl").unwrap();
+        let mismatches = rounds[0].replay(&alphabet, Vec::new());
+        assert_eq!(mismatches.len(), 1);
+        assert!(matches!(&mismatches[0], Mismatch::Feedback(guess, ..) if guess == "CRATE"));
+
+        // Test case 3: An edited word changes the feedback, the hint and the end of the round
+        let edited = text.replacen("CRANE", "CRONY", 1);
+        let rounds = read_transcript(edited.as_bytes(), "t.jsonl").unwrap();
+        let mismatches = rounds[0].replay(&alphabet, Vec::new());
+        assert!(matches!(
+            mismatches.last(),
+            Some(Mismatch::Status(GameStatus::Won, GameStatus::InProgress))
+        ));
+        assert!(mismatches
+            .iter()
+            .any(|mismatch| matches!(mismatch, Mismatch::Hint(3, 'N'))));
+
+        // Test case 4: A guess breaking the hard mode is reported, an abandoned round is not
+        let output = record("CRANE", true, |round| {
+            round.guess("crate");
+            round.guess("crave");
+            round.abandon();
+        });
+        let text = String::from_utf8(output)
+            .unwrap()
+            .replacen("CRAVE", "SLOTH", 1);
+        let rounds = read_transcript(text.as_bytes(), "t.jsonl").unwrap();
+        let mismatches = rounds[0].replay(&alphabet, Vec::new());
+        assert!(
+            matches!(mismatches.as_slice(), [Mismatch::Rejected(guess, _)] if guess == "SLOTH")
+        );
+
+        // Test case 5: Invalid transcripts are rejected with their line number
+        let text = "\n{\"type\":\"hint\",\"position\":0,\"character\":\"C\"}\n";
+        assert!(matches!(
+            read_transcript(text.as_bytes(), "t.jsonl"),
+            Err(Error::MissingRound(_, 2))
+        ));
+        assert!(matches!(
+            read_transcript("{\"type\":\"guess\"}".as_bytes(), "t.jsonl"),
+            Err(Error::Malformed(_, 1, _))
+        ));
+    }
+}
@@ -1,4 +1,8 @@
-use std::io;
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::Path;
+use std::time::Duration;
 
 pub mod format_and_check;
 
@@ -16,3 +20,149 @@ pub fn get_user_input_stdin() -> Result<String, io::Error> {
     io::stdin().read_line(&mut input)?;
     Ok(input)
 }
+
+/// A source of lines typed by the user, or standing for them.
+pub trait InputSource {
+    /// Reads the next line, without its line ending.
+    ///
+    /// # Returns
+    /// `None` once the input is exhausted.
+    ///
+    /// # Errors
+    /// Returns an error if the input cannot be read,
+    /// of kind `std::io::ErrorKind::Interrupted` if the user asked to stop, e.g. with Ctrl-C.
+    fn read_line(&mut self) -> io::Result<Option<String>>;
+
+    /// Reads the next line like `InputSource::read_line`, waiting at most `timeout` for it.
+    /// Sources that cannot wait for a limited time read the line without a timeout.
+    ///
+    /// # Errors
+    /// Returns an error of kind `std::io::ErrorKind::TimedOut` if no line came in time,
+    /// or the errors of `InputSource::read_line`.
+    fn read_line_timeout(&mut self, timeout: Duration) -> io::Result<Option<String>> {
+        let _ = timeout;
+        self.read_line()
+    }
+}
+
+impl<I: InputSource + ?Sized> InputSource for Box<I> {
+    fn read_line(&mut self) -> io::Result<Option<String>> {
+        (**self).read_line()
+    }
+
+    fn read_line_timeout(&mut self, timeout: Duration) -> io::Result<Option<String>> {
+        (**self).read_line_timeout(timeout)
+    }
+}
+
+/// Reads lines from any buffered reader.
+fn read_line_from(reader: &mut impl BufRead) -> io::Result<Option<String>> {
+    let mut line = String::new();
+    if reader.read_line(&mut line)? == 0 {
+        return Ok(None);
+    }
+    let trimmed_length = line.trim_end_matches(['\n', '\r']).len();
+    line.truncate(trimmed_length);
+    Ok(Some(line))
+}
+
+/// Reads the lines typed by the user on stdin.
+#[derive(Debug, Default)]
+pub struct StdinInput;
+
+impl InputSource for StdinInput {
+    fn read_line(&mut self) -> io::Result<Option<String>> {
+        read_line_from(&mut io::stdin().lock())
+    }
+}
+
+/// Replays lines given in advance, e.g. to script a game in tests.
+///
+/// # Examples
+/// ```
+/// use wordlers::user_input::{InputSource, ScriptedInput};
+///
+/// let mut input = ScriptedInput::from(vec![String::from("CRANE")]);
+/// assert_eq!(input.read_line().unwrap(), Some(String::from("CRANE")));
+/// assert_eq!(input.read_line().unwrap(), None);
+/// ```
+#[derive(Debug, Default)]
+pub struct ScriptedInput {
+    lines: VecDeque<String>,
+}
+
+impl From<Vec<String>> for ScriptedInput {
+    fn from(lines: Vec<String>) -> Self {
+        ScriptedInput {
+            lines: lines.into(),
+        }
+    }
+}
+
+impl InputSource for ScriptedInput {
+    fn read_line(&mut self) -> io::Result<Option<String>> {
+        Ok(self.lines.pop_front())
+    }
+}
+
+/// Reads lines from a file, e.g. a list of guesses prepared in advance.
+#[derive(Debug)]
+pub struct FileInput {
+    reader: BufReader<File>,
+}
+
+impl FileInput {
+    /// Opens the file at `path`.
+    ///
+    /// # Errors
+    /// Returns an error if the file cannot be opened.
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        Ok(FileInput {
+            reader: BufReader::new(File::open(path)?),
+        })
+    }
+}
+
+impl InputSource for FileInput {
+    fn read_line(&mut self) -> io::Result<Option<String>> {
+        read_line_from(&mut self.reader)
+    }
+}
+
+/// Reads lines from any buffered reader, e.g. a network connection.
+#[derive(Debug)]
+pub struct ReaderInput<R> {
+    reader: R,
+}
+
+impl<R: BufRead> ReaderInput<R> {
+    /// Reads the lines of the `reader`.
+    pub fn new(reader: R) -> Self {
+        ReaderInput { reader }
+    }
+}
+
+impl<R: BufRead> InputSource for ReaderInput<R> {
+    fn read_line(&mut self) -> io::Result<Option<String>> {
+        read_line_from(&mut self.reader)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn test_file_input() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        write!(file, "crane\r\n\ncrate").unwrap();
+
+        // Test case 1: Lines are read without their line ending, until the end of the file
+        let mut input = FileInput::open(file.path()).unwrap();
+        assert_eq!(input.read_line().unwrap(), Some(String::from("crane")));
+        assert_eq!(input.read_line().unwrap(), Some(String::new()));
+        assert_eq!(input.read_line().unwrap(), Some(String::from("crate")));
+        assert_eq!(input.read_line().unwrap(), None);
+    }
+}
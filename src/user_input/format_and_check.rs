@@ -1,3 +1,4 @@
+use crate::messages::{message, MessageId};
 use core::fmt;
 use std::error::Error;
 
@@ -6,8 +7,12 @@ use std::error::Error;
 pub enum UserInputError {
     /// The input does not correspond to the defined length. (expected length, given length)
     UnexpectedLength(usize, usize),
-    /// The input contains non-alphabetic characters. (not-alphabetic characters)
+    /// The input contains characters outside of the alphabet. (not-alphabetic characters)
     NotAlphabetic(Vec<char>),
+    /// In hard mode, the guess does not fit the feedback and hints received so far. (uppercased guess)
+    HardMode(String),
+    /// The guess is not a known word. (uppercased guess)
+    NotInDictionary(String),
 }
 
 impl Error for UserInputError {}
@@ -15,12 +20,31 @@ impl Error for UserInputError {}
 impl fmt::Display for UserInputError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            UserInputError::UnexpectedLength(expected, given) => {
-                write!(f, "Expected a string of {expected} characters, got {given}")
-            }
+            UserInputError::UnexpectedLength(expected, given) => write!(
+                f,
+                "{}",
+                message(
+                    MessageId::UserInputUnexpectedLength,
+                    &[("expected", expected), ("given", given)]
+                )
+            ),
             UserInputError::NotAlphabetic(not_alphabetic_characters) => write!(
                 f,
-                "Some of given input is not an alphabetic character: {not_alphabetic_characters:?}"
+                "{}",
+                message(
+                    MessageId::UserInputNotAlphabetic,
+                    &[("characters", &format!("{not_alphabetic_characters:?}"))]
+                )
+            ),
+            UserInputError::HardMode(guess) => write!(
+                f,
+                "{}",
+                message(MessageId::UserInputHardMode, &[("guess", guess)])
+            ),
+            UserInputError::NotInDictionary(guess) => write!(
+                f,
+                "{}",
+                message(MessageId::UserInputNotInDictionary, &[("guess", guess)])
             ),
         }
     }
@@ -52,3 +76,44 @@ pub fn input_string(input_str: &str, expected_length: usize) -> Result<String, U
         Ok(trimmed_input_str.to_ascii_uppercase())
     }
 }
+
+/// Formats and checks the input string for validity against an `alphabet` of uppercase characters,
+/// such as the one of a language pack.
+/// Returns the formatted string if it is valid, or an error otherwise.
+///
+/// # Errors
+///
+/// Returns an error if the input string is not the expected length or contains characters outside of the `alphabet`.
+///
+/// # Examples
+///
+/// ```
+/// use wordlers::user_input::format_and_check::input_string_in_alphabet;
+///
+/// let alphabet: Vec<char> = "ABCDEFGHIJKLMNÑOPQRSTUVWXYZ".chars().collect();
+/// assert_eq!(input_string_in_alphabet(" niño\n", 4, &alphabet).unwrap(), "NIÑO");
+/// assert!(input_string_in_alphabet("crème", 5, &alphabet).is_err());
+/// ```
+pub fn input_string_in_alphabet(
+    input_str: &str,
+    expected_length: usize,
+    alphabet: &[char],
+) -> Result<String, UserInputError> {
+    let uppercased_input_str = input_str.trim().to_uppercase();
+    let trimmed_input_length = uppercased_input_str.chars().count();
+
+    let not_alphabetic_characters = uppercased_input_str
+        .chars()
+        .filter(|c| !alphabet.contains(c))
+        .collect::<Vec<char>>();
+    if trimmed_input_length != expected_length {
+        Err(UserInputError::UnexpectedLength(
+            expected_length,
+            trimmed_input_length,
+        ))
+    } else if !not_alphabetic_characters.is_empty() {
+        Err(UserInputError::NotAlphabetic(not_alphabetic_characters))
+    } else {
+        Ok(uppercased_input_str)
+    }
+}
@@ -0,0 +1,447 @@
+use crate::data_loader::compiled::CompiledDictionary;
+use crate::data_loader::{Error, LoadedWords, WordHashMap, DEFAULT_FREQUENCY};
+use rand::distributions::{Distribution, WeightedIndex};
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+use std::hash::BuildHasher;
+
+/// Words organized by length, whatever their storage.
+///
+/// Words are uppercase, and each of them has a frequency (higher is more common).
+pub trait WordSource {
+    /// Returns the lengths of the words, in increasing order.
+    fn lengths(&self) -> Vec<usize>;
+
+    /// Returns the words of `length` characters with their frequencies.
+    fn words(&self, length: usize) -> Box<dyn Iterator<Item = (Cow<'_, str>, f64)> + '_>;
+
+    /// Returns the number of words of `length` characters.
+    fn count(&self, length: usize) -> usize {
+        self.words(length).count()
+    }
+
+    /// Returns the frequency of the uppercased `word`, or `None` if it is not in the source.
+    fn frequency(&self, word: &str) -> Option<f64> {
+        self.words(word.chars().count())
+            .find(|(known_word, _)| known_word == word)
+            .map(|(_, frequency)| frequency)
+    }
+
+    /// Checks whether the uppercased `word` is in the source.
+    fn contains(&self, word: &str) -> bool {
+        self.frequency(word).is_some()
+    }
+
+    /// Chooses a random word of `length` characters with the supplied `rng`.
+    ///
+    /// Words are weighted by their frequency, so that common words are chosen more often.
+    /// If every word has a frequency of 0, they are chosen uniformly.
+    /// The words are sorted first, so that a seeded `rng` always chooses the same word, whatever the storage.
+    ///
+    /// # Errors
+    ///
+    /// - `wordlers::data_loader::Error::NoWordThisLength` if there is no word of this length.
+    /// - `wordlers::data_loader::Error::EmptyWordVec` if the words of this length were all removed.
+    fn choose_random<R: Rng + ?Sized>(&self, length: usize, rng: &mut R) -> Result<String, Error>
+    where
+        Self: Sized,
+    {
+        if !self.lengths().contains(&length) {
+            return Err(Error::NoWordThisLength(length));
+        }
+        let mut words: Vec<(Cow<'_, str>, f64)> = self.words(length).collect();
+        words.sort_by(|(word_a, _), (word_b, _)| word_a.cmp(word_b));
+        let chosen = match WeightedIndex::new(words.iter().map(|(_, frequency)| *frequency)) {
+            Ok(distribution) => words.get(distribution.sample(rng)),
+            Err(_) => words.choose(rng),
+        };
+        match chosen {
+            None => Err(Error::EmptyWordVec(length)),
+            Some((word, _)) => Ok(word.to_string()),
+        }
+    }
+}
+
+impl<S: BuildHasher> WordSource for HashMap<usize, HashMap<String, f64, S>, S> {
+    fn lengths(&self) -> Vec<usize> {
+        let mut lengths: Vec<usize> = self.keys().copied().collect();
+        lengths.sort_unstable();
+        lengths
+    }
+
+    fn words(&self, length: usize) -> Box<dyn Iterator<Item = (Cow<'_, str>, f64)> + '_> {
+        Box::new(
+            self.get(&length)
+                .into_iter()
+                .flatten()
+                .map(|(word, frequency)| (Cow::Borrowed(word.as_str()), *frequency)),
+        )
+    }
+
+    fn count(&self, length: usize) -> usize {
+        self.get(&length).map_or(0, HashMap::len)
+    }
+
+    fn frequency(&self, word: &str) -> Option<f64> {
+        self.get(&word.chars().count())?.get(word).copied()
+    }
+
+    fn contains(&self, word: &str) -> bool {
+        self.get(&word.chars().count())
+            .is_some_and(|words| words.contains_key(word))
+    }
+}
+
+/// Words stored in sorted vectors, one per length, looked up by binary search.
+///
+/// It uses less memory than a `WordHashMap`, and iterates in alphabetical order.
+#[derive(Debug, Default)]
+pub struct SortedWordStore {
+    words: BTreeMap<usize, Vec<(String, f64)>>,
+}
+
+impl SortedWordStore {
+    /// Creates a store from `(word, frequency)` pairs. Duplicate words keep their highest frequency.
+    pub fn new(words: impl IntoIterator<Item = (String, f64)>) -> Self {
+        let mut store = SortedWordStore::default();
+        for (word, frequency) in words {
+            store
+                .words
+                .entry(word.chars().count())
+                .or_default()
+                .push((word, frequency));
+        }
+        for words in store.words.values_mut() {
+            words.sort_by(|(word_a, frequency_a), (word_b, frequency_b)| {
+                word_a.cmp(word_b).then(frequency_b.total_cmp(frequency_a))
+            });
+            words.dedup_by(|(word_a, _), (word_b, _)| word_a == word_b);
+        }
+        store
+    }
+}
+
+impl From<WordHashMap> for SortedWordStore {
+    fn from(word_hashmap: WordHashMap) -> Self {
+        SortedWordStore::new(word_hashmap.into_values().flatten())
+    }
+}
+
+impl WordSource for SortedWordStore {
+    fn lengths(&self) -> Vec<usize> {
+        self.words.keys().copied().collect()
+    }
+
+    fn words(&self, length: usize) -> Box<dyn Iterator<Item = (Cow<'_, str>, f64)> + '_> {
+        Box::new(
+            self.words
+                .get(&length)
+                .into_iter()
+                .flatten()
+                .map(|(word, frequency)| (Cow::Borrowed(word.as_str()), *frequency)),
+        )
+    }
+
+    fn count(&self, length: usize) -> usize {
+        self.words.get(&length).map_or(0, Vec::len)
+    }
+
+    fn frequency(&self, word: &str) -> Option<f64> {
+        let words = self.words.get(&word.chars().count())?;
+        let index = words
+            .binary_search_by(|(known_word, _)| known_word.as_str().cmp(word))
+            .ok()?;
+        Some(words[index].1)
+    }
+}
+
+/// A plain list of words, all with `DEFAULT_FREQUENCY`, e.g. to write tests.
+///
+/// # Examples
+///
+/// ```
+/// use wordlers::word_source::{WordList, WordSource};
+///
+/// let words = WordList::from(["CRANE", "CRATE", "CAT"]);
+/// assert_eq!(words.lengths(), vec![3, 5]);
+/// assert!(words.contains("CRATE"));
+/// ```
+#[derive(Debug, Default)]
+pub struct WordList(pub Vec<String>);
+
+impl<const N: usize> From<[&str; N]> for WordList {
+    fn from(words: [&str; N]) -> Self {
+        WordList(words.into_iter().map(String::from).collect())
+    }
+}
+
+impl WordSource for WordList {
+    fn lengths(&self) -> Vec<usize> {
+        let mut lengths: Vec<usize> = self.0.iter().map(|word| word.chars().count()).collect();
+        lengths.sort_unstable();
+        lengths.dedup();
+        lengths
+    }
+
+    fn words(&self, length: usize) -> Box<dyn Iterator<Item = (Cow<'_, str>, f64)> + '_> {
+        Box::new(
+            self.0
+                .iter()
+                .filter(move |word| word.chars().count() == length)
+                .map(|word| (Cow::Borrowed(word.as_str()), DEFAULT_FREQUENCY)),
+        )
+    }
+}
+
+/// Packed words have to be decoded to be listed, so that `words` builds a `String` per word.
+/// The other methods work on the packed words, and `choose_random` only decodes the chosen one.
+impl<B: AsRef<[u8]>> WordSource for CompiledDictionary<B> {
+    fn lengths(&self) -> Vec<usize> {
+        CompiledDictionary::lengths(self).collect()
+    }
+
+    fn words(&self, length: usize) -> Box<dyn Iterator<Item = (Cow<'_, str>, f64)> + '_> {
+        Box::new(
+            CompiledDictionary::words(self, length)
+                .map(|(word, frequency)| (Cow::Owned(word), frequency)),
+        )
+    }
+
+    fn count(&self, length: usize) -> usize {
+        CompiledDictionary::count(self, length)
+    }
+
+    fn frequency(&self, word: &str) -> Option<f64> {
+        CompiledDictionary::frequency(self, word)
+    }
+
+    fn contains(&self, word: &str) -> bool {
+        CompiledDictionary::contains(self, word)
+    }
+
+    /// Chooses a random word as the other sources do: the words are stored sorted,
+    /// so that the same `rng` chooses the same word.
+    fn choose_random<R: Rng + ?Sized>(&self, length: usize, rng: &mut R) -> Result<String, Error>
+    where
+        Self: Sized,
+    {
+        if CompiledDictionary::count(self, length) == 0 {
+            return Err(Error::NoWordThisLength(length));
+        }
+        let index = match WeightedIndex::new(self.frequencies(length)) {
+            Ok(distribution) => distribution.sample(rng),
+            Err(_) => {
+                let indices: Vec<usize> = (0..CompiledDictionary::count(self, length)).collect();
+                *indices.choose(rng).ok_or(Error::EmptyWordVec(length))?
+            }
+        };
+        self.word(length, index).ok_or(Error::EmptyWordVec(length))
+    }
+}
+
+impl WordSource for LoadedWords {
+    fn lengths(&self) -> Vec<usize> {
+        match self {
+            LoadedWords::List(words) => words.lengths(),
+            LoadedWords::Compiled(dictionary) => WordSource::lengths(dictionary),
+        }
+    }
+
+    fn words(&self, length: usize) -> Box<dyn Iterator<Item = (Cow<'_, str>, f64)> + '_> {
+        match self {
+            LoadedWords::List(words) => words.words(length),
+            LoadedWords::Compiled(dictionary) => WordSource::words(dictionary, length),
+        }
+    }
+
+    fn count(&self, length: usize) -> usize {
+        match self {
+            LoadedWords::List(words) => WordSource::count(words, length),
+            LoadedWords::Compiled(dictionary) => WordSource::count(dictionary, length),
+        }
+    }
+
+    fn frequency(&self, word: &str) -> Option<f64> {
+        match self {
+            LoadedWords::List(words) => words.frequency(word),
+            LoadedWords::Compiled(dictionary) => WordSource::frequency(dictionary, word),
+        }
+    }
+
+    fn contains(&self, word: &str) -> bool {
+        match self {
+            LoadedWords::List(words) => WordSource::contains(words, word),
+            LoadedWords::Compiled(dictionary) => WordSource::contains(dictionary, word),
+        }
+    }
+
+    fn choose_random<R: Rng + ?Sized>(&self, length: usize, rng: &mut R) -> Result<String, Error>
+    where
+        Self: Sized,
+    {
+        match self {
+            LoadedWords::List(words) => words.choose_random(length, rng),
+            LoadedWords::Compiled(dictionary) => dictionary.choose_random(length, rng),
+        }
+    }
+}
+
+/// The words of another source whose frequency is at least `min_frequency`, filtered as they are read,
+/// e.g. to leave obscure words out of a compiled dictionary without copying it.
+///
+/// # Examples
+///
+/// ```
+/// use std::collections::HashMap;
+/// use wordlers::data_loader::WordHashMap;
+/// use wordlers::word_source::{MinFrequency, WordSource};
+///
+/// let words = WordHashMap::from([(
+///     5,
+///     HashMap::from([(String::from("CRANE"), 3.0), (String::from("CRWTH"), 0.1)]),
+/// )]);
+/// let common_words = MinFrequency::new(&words, 1.0);
+/// assert!(common_words.contains("CRANE"));
+/// assert!(!common_words.contains("CRWTH"));
+/// assert_eq!(common_words.count(5), 1);
+/// ```
+#[derive(Debug)]
+pub struct MinFrequency<'w, W: WordSource> {
+    source: &'w W,
+    min_frequency: f64,
+}
+
+impl<'w, W: WordSource> MinFrequency<'w, W> {
+    /// Keeps the `words` whose frequency is at least `min_frequency`.
+    #[must_use]
+    pub fn new(words: &'w W, min_frequency: f64) -> Self {
+        MinFrequency {
+            source: words,
+            min_frequency,
+        }
+    }
+}
+
+impl<W: WordSource> WordSource for MinFrequency<'_, W> {
+    /// Returns the lengths with words left, as `wordlers::data_loader::retain_min_frequency` keeps them.
+    fn lengths(&self) -> Vec<usize> {
+        self.source
+            .lengths()
+            .into_iter()
+            .filter(|length| self.words(*length).next().is_some())
+            .collect()
+    }
+
+    fn words(&self, length: usize) -> Box<dyn Iterator<Item = (Cow<'_, str>, f64)> + '_> {
+        Box::new(
+            self.source
+                .words(length)
+                .filter(|(_, frequency)| *frequency >= self.min_frequency),
+        )
+    }
+
+    fn frequency(&self, word: &str) -> Option<f64> {
+        self.source
+            .frequency(word)
+            .filter(|frequency| *frequency >= self.min_frequency)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data_loader::compiled::compile;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    /// Checks the behaviour shared by every word source holding `CAT`, `CRANE` and `CRATE`.
+    fn check_word_source(words: &impl WordSource) {
+        assert_eq!(words.lengths(), vec![3, 5]);
+        assert_eq!(words.count(5), 2);
+        assert_eq!(words.count(4), 0);
+        assert!(words.contains("CRANE"));
+        assert!(!words.contains("CRAN"));
+        assert!(!words.contains("TRACE"));
+
+        let mut rng = StdRng::seed_from_u64(0);
+        let chosen = words.choose_random(5, &mut rng).unwrap();
+        assert!(chosen == "CRANE" || chosen == "CRATE");
+        assert_eq!(
+            words.choose_random(4, &mut rng).unwrap_err().to_string(),
+            "No word of length 4 found."
+        );
+    }
+
+    #[test]
+    fn test_word_sources() {
+        let word_hashmap = WordHashMap::from([
+            (3, HashMap::from([("CAT".to_string(), 1.0)])),
+            (
+                5,
+                HashMap::from([("CRANE".to_string(), 2.0), ("CRATE".to_string(), 1.0)]),
+            ),
+        ]);
+
+        // Test case 1: Hash map
+        check_word_source(&word_hashmap);
+
+        // Test case 2: Sorted vectors
+        check_word_source(&SortedWordStore::from(word_hashmap.clone()));
+
+        // Test case 3: Plain list
+        check_word_source(&WordList::from(["CRATE", "CAT", "CRANE"]));
+
+        // Test case 4: Compiled dictionary
+        let bytes = compile(&word_hashmap).unwrap();
+        let dictionary = CompiledDictionary::from_bytes(bytes, "test").unwrap();
+        check_word_source(&dictionary);
+        assert_eq!(WordSource::frequency(&dictionary, "CRATE"), Some(1.0));
+
+        // Test case 5: Words of a source filtered by frequency
+        let mut rare_words = word_hashmap.clone();
+        rare_words.insert(4, HashMap::from([("CRAW".to_string(), 0.5)]));
+        rare_words
+            .get_mut(&5)
+            .unwrap()
+            .insert("CRAWL".to_string(), 0.5);
+        check_word_source(&MinFrequency::new(&rare_words, 1.0));
+        assert_eq!(MinFrequency::new(&rare_words, 1.0).frequency("CRAWL"), None);
+    }
+
+    #[test]
+    fn test_choose_random_is_reproducible() {
+        let words = SortedWordStore::new(
+            ["ALPHA", "BRAVO", "CHARL", "DELTA", "ECHOS"]
+                .into_iter()
+                .map(|word| (String::from(word), DEFAULT_FREQUENCY)),
+        );
+
+        // Test case 1: The same seed gives the same word
+        let first = words.choose_random(5, &mut StdRng::seed_from_u64(42));
+        let second = words.choose_random(5, &mut StdRng::seed_from_u64(42));
+        assert_eq!(first.as_ref().unwrap(), second.as_ref().unwrap());
+
+        // Test case 2: Whatever the storage, even when only the chosen word is decoded
+        let word_hashmap: WordHashMap = WordHashMap::from([(
+            5,
+            words
+                .words(5)
+                .map(|(word, frequency)| (word.into_owned(), frequency))
+                .collect(),
+        )]);
+        let dictionary =
+            CompiledDictionary::from_bytes(compile(&word_hashmap).unwrap(), "test").unwrap();
+        for seed in 0..20 {
+            assert_eq!(
+                dictionary
+                    .choose_random(5, &mut StdRng::seed_from_u64(seed))
+                    .unwrap(),
+                word_hashmap
+                    .choose_random(5, &mut StdRng::seed_from_u64(seed))
+                    .unwrap()
+            );
+        }
+    }
+}
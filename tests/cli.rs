@@ -0,0 +1,612 @@
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpStream;
+use std::process::{Child, Command, Output, Stdio};
+use tempfile::{NamedTempFile, TempDir};
+
+const SEED: &str = "42";
+const WORDS: &str = "crane\ncrate\ntrace\nslate\nabout\nhouse\n";
+
+/// Removes the ANSI escape sequences used to colour the output.
+fn strip_colours(text: &str) -> String {
+    let mut stripped = String::new();
+    let mut characters = text.chars();
+    while let Some(character) = characters.next() {
+        if character == '\u{1b}' {
+            for character in characters.by_ref() {
+                if character.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        } else {
+            stripped.push(character);
+        }
+    }
+    stripped
+}
+
+/// The result of a run of the binary, with colours stripped from its output.
+struct Run {
+    code: Option<i32>,
+    stdout: String,
+    stderr: String,
+}
+
+/// Runs the `wordlers` binary with `args`, in English, without user language packs
+/// and with a fresh stats file, feeding it `stdin`.
+fn run(args: &[&str], stdin: &str) -> Run {
+    let lang_dir = TempDir::new().unwrap();
+    let mut child = Command::new(env!("CARGO_BIN_EXE_wordlers"))
+        .args(["--ui-lang", "en"])
+        .args(args)
+        .env("WORDLERS_LANG_DIR", lang_dir.path())
+        .env("WORDLERS_STATS_FILE", lang_dir.path().join("stats.json"))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(stdin.as_bytes())
+        .unwrap();
+    let Output {
+        status,
+        stdout,
+        stderr,
+    } = child.wait_with_output().unwrap();
+    Run {
+        code: status.code(),
+        stdout: strip_colours(&String::from_utf8_lossy(&stdout)),
+        stderr: strip_colours(&String::from_utf8_lossy(&stderr)),
+    }
+}
+
+/// Writes the test dictionary to a temporary file.
+fn dictionary() -> NamedTempFile {
+    let mut file = NamedTempFile::new().unwrap();
+    write!(file, "{WORDS}").unwrap();
+    file
+}
+
+/// Plays a game with the test dictionary and `SEED`, feeding it `stdin`.
+fn play(stdin: &str) -> Run {
+    let file = dictionary();
+    run(
+        &["-f", file.path().to_str().unwrap(), "--seed", SEED],
+        stdin,
+    )
+}
+
+/// Finds the word chosen with `SEED`, by losing a game.
+fn secret_word() -> String {
+    let run = play("");
+    let line = run
+        .stdout
+        .lines()
+        .find(|line| line.contains("the word was"))
+        .unwrap()
+        .to_string();
+    line.trim_end_matches('.')
+        .rsplit(' ')
+        .next()
+        .unwrap()
+        .to_string()
+}
+
+/// Returns a word of the test dictionary other than `word`.
+fn other_word(word: &str) -> &'static str {
+    WORDS
+        .lines()
+        .find(|candidate| !candidate.eq_ignore_ascii_case(word))
+        .unwrap()
+}
+
+#[test]
+fn test_seed_chooses_the_same_word() {
+    let word = secret_word();
+    assert!(WORDS.lines().any(|known| known.eq_ignore_ascii_case(&word)));
+    assert_eq!(secret_word(), word);
+}
+
+#[test]
+fn test_win() {
+    let word = secret_word();
+    let run = play(&format!("{}\n{word}\n", other_word(&word)));
+
+    assert_eq!(run.code, Some(0));
+    assert!(run
+        .stdout
+        .contains(&format!("Congratulations, the word was {word}, you won!")));
+    assert!(run.stdout.contains("Current tries:"));
+    assert!(run.stdout.contains(&word.to_uppercase()));
+    assert!(run.stdout.contains("Score: 80, with 0 hints."));
+}
+
+#[test]
+fn test_hard_mode() {
+    let mut words = NamedTempFile::new().unwrap();
+    writeln!(words, "crane").unwrap();
+    let run = run(
+        &["-f", words.path().to_str().unwrap(), "--hard"],
+        "trace\nhouse\ncrane\n",
+    );
+
+    assert_eq!(run.code, Some(0));
+    assert!(run.stderr.contains("In hard mode"));
+    assert!(run.stderr.contains("HOUSE does not"));
+    assert_eq!(run.stdout.matches("Current tries:").count(), 2);
+    assert!(run
+        .stdout
+        .contains("Score: 120, with 0 hints, in hard mode."));
+}
+
+#[test]
+fn test_loss() {
+    let word = secret_word();
+    let guesses = format!("{}\n", other_word(&word)).repeat(5);
+    let run = play(&guesses);
+
+    assert_eq!(run.code, Some(0));
+    assert!(run
+        .stdout
+        .contains(&format!("You lost, the word was {word}.")));
+    assert_eq!(run.stdout.matches("Current tries:").count(), 5);
+}
+
+#[test]
+fn test_invalid_guesses() {
+    let word = secret_word();
+    let run = play(&format!("ab\ncr4ne\nqwxyz\n{word}\n"));
+
+    assert_eq!(run.code, Some(0));
+    assert!(run
+        .stderr
+        .contains("Expected a string of 5 characters, got 2"));
+    assert!(run.stderr.contains("'4'"));
+    assert!(run.stderr.contains("QWXYZ is not in the list of words."));
+    assert_eq!(run.stdout.matches("Current tries:").count(), 1);
+    assert!(run.stdout.contains("you won!"));
+}
+
+#[test]
+fn test_pack_without_guess_list() {
+    let mut words = NamedTempFile::new().unwrap();
+    writeln!(words, "route").unwrap();
+    let run = run(
+        &["--lang", "fr", "-f", words.path().to_str().unwrap()],
+        "ronde\nroute\n",
+    );
+
+    // The fr pack has no guess list, so any word of its alphabet is accepted
+    assert_eq!(run.code, Some(0));
+    assert!(!run.stderr.contains("not in the list of words"));
+    assert_eq!(run.stdout.matches("Current tries:").count(), 2);
+}
+
+#[test]
+fn test_ui_lang_from_pack() {
+    let mut words = NamedTempFile::new().unwrap();
+    writeln!(words, "route").unwrap();
+    let lang_dir = TempDir::new().unwrap();
+    let mut child = Command::new(env!("CARGO_BIN_EXE_wordlers"))
+        .args(["--lang", "fr", "-f", words.path().to_str().unwrap()])
+        .env("WORDLERS_LANG_DIR", lang_dir.path())
+        .env("WORDLERS_STATS_FILE", lang_dir.path().join("stats.json"))
+        .env_remove("LC_ALL")
+        .env_remove("LC_MESSAGES")
+        .env("LANG", "C")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(b"route\n").unwrap();
+    let output = child.wait_with_output().unwrap();
+
+    // Without a language from the command line or the locale, the messages follow the pack
+    assert!(output.status.success());
+    let stdout = strip_colours(&String::from_utf8_lossy(&output.stdout));
+    assert!(stdout.contains("Veuillez saisir une nouvelle proposition :"));
+    assert!(stdout.contains("le mot était ROUTE, vous avez gagné !"));
+}
+
+#[test]
+fn test_end_of_input() {
+    let word = secret_word();
+    let run = play(&format!("{}\n", other_word(&word)));
+
+    assert_eq!(run.code, Some(3));
+    assert!(run
+        .stdout
+        .contains(&format!("Game abandoned, the word was {word}.")));
+    assert_eq!(run.stdout.matches("Current tries:").count(), 1);
+}
+
+#[test]
+fn test_race() {
+    let word = secret_word();
+    let miss = other_word(&word);
+    let file = dictionary();
+    let run = run(
+        &[
+            "-f",
+            file.path().to_str().unwrap(),
+            "--seed",
+            SEED,
+            "--race",
+            "--players",
+            "alice,bob",
+        ],
+        &format!("{miss}\n{word}\n{}", format!("{miss}\n").repeat(4)),
+    );
+
+    assert_eq!(run.code, Some(0));
+    assert_eq!(run.stdout.matches("alice, your turn.").count(), 5);
+    assert_eq!(run.stdout.matches("bob, your turn.").count(), 1);
+    assert!(!run.stdout.contains("you won!"));
+    assert!(run.stdout.contains(&format!("The word was {word}.")));
+    assert!(run.stdout.contains("1. bob: found in 1 guesses and"));
+    assert!(run.stdout.contains("2. alice: not found"));
+    assert!(run.stdout.contains("bob wins the race!"));
+}
+
+#[test]
+fn test_survival() {
+    let mut words = NamedTempFile::new().unwrap();
+    writeln!(words, "crane").unwrap();
+    let stats_dir = TempDir::new().unwrap();
+    let stats_file = stats_dir.path().join("wordlers").join("stats.json");
+    let survive = |stdin: &str| {
+        run(
+            &[
+                "-f",
+                words.path().to_str().unwrap(),
+                "--survival",
+                "--stats-file",
+                stats_file.to_str().unwrap(),
+            ],
+            stdin,
+        )
+    };
+
+    // Two words found at once carry 4 then 8 attempts over, and the third is lost in 13
+    let run = survive(&format!("crane\ncrane\n{}", "house\n".repeat(13)));
+    assert_eq!(run.code, Some(0));
+    assert!(run.stdout.contains("Word 1, in 5 attempts"));
+    assert!(run.stdout.contains("Word 2, in 9 attempts"));
+    assert!(run.stdout.contains("Word 3, in 13 attempts"));
+    assert!(run.stdout.contains("Run over: 2 words found, score 14."));
+    assert!(run.stdout.contains("New best run!"));
+    let stats = std::fs::read_to_string(&stats_file).unwrap();
+    assert!(stats.contains(r#""score": 14"#));
+
+    // A shorter run keeps the best one
+    let run = survive("house\n");
+    assert_eq!(run.code, Some(3));
+    assert!(run.stdout.contains("Run over: 0 words found, score 0."));
+    assert!(run.stdout.contains("Best run: 2 words, score 14."));
+}
+
+#[test]
+fn test_leaderboard() {
+    let mut words = NamedTempFile::new().unwrap();
+    writeln!(words, "crane").unwrap();
+    let stats_dir = TempDir::new().unwrap();
+    let stats_file = stats_dir.path().join("stats.json");
+    let stats_file = stats_file.to_str().unwrap();
+    let play_as = |player: &str, stdin: &str| {
+        let words = words.path().to_str().unwrap();
+        let args = ["-f", words, "--stats-file", stats_file, "--player", player];
+        assert_eq!(run(&args, stdin).code, Some(0));
+    };
+    play_as("alice", "crane\n");
+    play_as("alice", &"house\n".repeat(5));
+    play_as("bob", "trace\ncrane\n");
+    play_as("bob", "crane\n");
+
+    // Players are ranked by win rate, each with their own games
+    let run_leaderboard = |args: &[&str]| {
+        let mut all_args = vec!["--stats-file", stats_file, "leaderboard"];
+        all_args.extend_from_slice(args);
+        run(&all_args, "")
+    };
+    let leaderboard = run_leaderboard(&[]);
+    assert_eq!(leaderboard.code, Some(0));
+    assert_eq!(
+        leaderboard.stdout.lines().collect::<Vec<&str>>(),
+        [
+            "1. bob: 100 % won of 2 games, 1.50 guesses on average, best streak 2",
+            "2. alice: 50 % won of 2 games, 1.00 guesses on average, best streak 1"
+        ]
+    );
+
+    // Games can be filtered by word length and mode
+    let leaderboard = run_leaderboard(&["--length", "5", "--mode", "classic"]);
+    assert!(leaderboard.stdout.starts_with("1. bob"));
+    let leaderboard = run_leaderboard(&["--mode", "survival"]);
+    assert_eq!(leaderboard.stdout.trim(), "No game recorded yet.");
+    let leaderboard = run_leaderboard(&["--mode", "zen"]);
+    assert_eq!(leaderboard.code, Some(2));
+    assert!(leaderboard
+        .stderr
+        .contains("Available modes: classic, timed"));
+}
+
+#[test]
+fn test_transcript_replay() {
+    let mut words = NamedTempFile::new().unwrap();
+    writeln!(words, "crane").unwrap();
+    let transcript_dir = TempDir::new().unwrap();
+    let transcript = transcript_dir.path().join("rounds.jsonl");
+    let transcript = transcript.to_str().unwrap();
+    let words = words.path().to_str().unwrap();
+    let args = ["-f", words, "--seed", SEED, "--transcript", transcript];
+    assert_eq!(
+        run(
+            &args,
+            "slate
+?
+crane
+"
+        )
+        .code,
+        Some(0)
+    );
+    assert_eq!(
+        run(
+            &args, "house
+"
+        )
+        .code,
+        Some(3)
+    );
+
+    // Each round is appended to the transcript, and replays without mismatch
+    let text = std::fs::read_to_string(transcript).unwrap();
+    assert_eq!(text.lines().count(), 8);
+    assert!(text.starts_with(r#"{"type":"round","word":"CRANE","max_attempts":5,"#));
+    let replay = run(&["replay", transcript], "");
+    assert_eq!(replay.code, Some(0));
+    assert!(replay.stdout.contains("Round 2, played by"));
+    assert!(replay.stdout.contains("Hint: letter 1 is C."));
+    assert!(replay
+        .stdout
+        .ends_with("2 rounds replayed, every step matches the rules.\n"));
+
+    // An edited feedback is reported
+    let edited = text.replacen(
+        r#"{"state":"not_present","character":"S"}"#,
+        r#"{"state":"good","character":"S"}"#,
+        1,
+    );
+    std::fs::write(transcript, edited).unwrap();
+    let replay = run(&["replay", transcript], "");
+    assert_eq!(replay.code, Some(4));
+    assert_eq!(
+        replay.stderr,
+        "SLATE: the stored feedback G.G.G differs from the rules' ..G.G.\n\
+         1 steps of the transcript do not match the rules.\n"
+    );
+}
+
+#[test]
+fn test_missing_words_file() {
+    let run = run(&["-f", "/nonexistent/words.txt", "--seed", SEED], "");
+
+    assert_eq!(run.code, Some(5));
+    assert!(run.stderr.contains("/nonexistent/words.txt"));
+    assert!(!run.stdout.contains("Current tries:"));
+}
+
+#[test]
+fn test_unavailable_length() {
+    let file = dictionary();
+    let run = run(
+        &[
+            "-f",
+            file.path().to_str().unwrap(),
+            "-n",
+            "7",
+            "--seed",
+            SEED,
+        ],
+        "",
+    );
+
+    assert_eq!(run.code, Some(4));
+    assert!(run
+        .stderr
+        .contains("No word of length 7 found. Available lengths: 5."));
+}
+
+#[test]
+fn test_dict_check() {
+    let file = dictionary();
+    let path = file.path().to_str().unwrap();
+
+    let run_present = run(&["dict", "check", path, "Crane"], "");
+    assert_eq!(run_present.code, Some(0));
+    assert!(run_present.stdout.contains("CRANE is in"));
+
+    let run_absent = run(&["dict", "check", path, "zebra"], "");
+    assert_eq!(run_absent.code, Some(1));
+    assert!(run_absent.stdout.contains("ZEBRA is not in"));
+}
+
+#[test]
+fn test_dict_commands_on_compiled() {
+    let file = dictionary();
+    let output_dir = TempDir::new().unwrap();
+    let compiled = output_dir.path().join("words.bin");
+    let compiled = compiled.to_str().unwrap();
+    let built = output_dir.path().join("words.txt");
+    let built = built.to_str().unwrap();
+    let blocklist = NamedTempFile::new().unwrap();
+    writeln!(blocklist.as_file(), "slate\nhouse").unwrap();
+    let compiled_blocklist = output_dir.path().join("blocklist.bin");
+    let compiled_blocklist = compiled_blocklist.to_str().unwrap();
+    let compile = |input: &str, output: &str| {
+        assert_eq!(
+            run(&["dict", "compile", input, "-o", output], "").code,
+            Some(0)
+        );
+    };
+    compile(file.path().to_str().unwrap(), compiled);
+    compile(blocklist.path().to_str().unwrap(), compiled_blocklist);
+
+    let checked = run(&["dict", "check", compiled, "crane"], "");
+    assert_eq!(checked.code, Some(0));
+    assert!(checked.stdout.contains("CRANE is in"));
+
+    // Compiled dictionaries are accepted as inputs and blocklists
+    let run = run(
+        &[
+            "dict",
+            "build",
+            compiled,
+            "--blocklist",
+            compiled_blocklist,
+            "-o",
+            built,
+        ],
+        "",
+    );
+    assert_eq!(run.code, Some(0));
+    assert_eq!(
+        std::fs::read_to_string(built).unwrap(),
+        "about\ncrane\ncrate\ntrace\n"
+    );
+}
+
+#[test]
+fn test_json_protocol() {
+    let file = dictionary();
+    let requests = concat!(
+        r#"{"type": "new_game", "seed": 1}"#,
+        "\n",
+        r#"{"type": "guess", "word": "cr4ne"}"#,
+        "\n",
+        r#"{"type": "guess", "word": "crane"}"#,
+        "\n",
+        r#"{"type": "quit"}"#,
+        "\n",
+    );
+    let run = run(
+        &["-f", file.path().to_str().unwrap(), "--protocol", "json"],
+        requests,
+    );
+
+    assert_eq!(run.code, Some(0));
+    let lines: Vec<&str> = run.stdout.lines().collect();
+    assert_eq!(lines.len(), 4);
+    assert_eq!(
+        lines[0],
+        r#"{"type":"game_started","length":5,"max_attempts":5}"#
+    );
+    assert!(lines[1].starts_with(r#"{"type":"error","code":"invalid_guess""#));
+    assert!(lines[2].starts_with(r#"{"type":"feedback","guess":"CRANE""#));
+    assert!(lines[2].contains(r#""remaining_attempts":4"#));
+    assert!(lines[3].starts_with(r#"{"type":"goodbye""#));
+}
+
+/// Sends a JSON `request` to a game server and returns the next line it sends back.
+fn exchange(stream: &mut BufReader<TcpStream>, request: &str) -> String {
+    if !request.is_empty() {
+        writeln!(stream.get_mut(), "{request}").unwrap();
+    }
+    let mut line = String::new();
+    stream.read_line(&mut line).unwrap();
+    line
+}
+
+/// Starts a server choosing its words in the `words` file, with the extra `args`, on a free port.
+///
+/// # Returns
+/// The server process, to be killed at the end of the test, and its port.
+fn serve(words: &NamedTempFile, args: &[&str]) -> (Child, String) {
+    let lang_dir = TempDir::new().unwrap();
+    let mut server = Command::new(env!("CARGO_BIN_EXE_wordlers"))
+        .args(["--ui-lang", "en", "-f", words.path().to_str().unwrap()])
+        .args(args)
+        .args(["serve", "--port", "0"])
+        .env("WORDLERS_LANG_DIR", lang_dir.path())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    let port = BufReader::new(server.stdout.take().unwrap())
+        .lines()
+        .find_map(|line| {
+            line.unwrap()
+                .strip_prefix("Listening on port ")
+                .map(|port| port.trim_end_matches('.').to_string())
+        })
+        .unwrap();
+    (server, port)
+}
+
+#[test]
+fn test_serve() {
+    let mut file = NamedTempFile::new().unwrap();
+    writeln!(file, "crane").unwrap();
+    let (mut server, port) = serve(&file, &[]);
+    let connect = || BufReader::new(TcpStream::connect(format!("127.0.0.1:{port}")).unwrap());
+    let mut alice = connect();
+    let mut bob = connect();
+
+    let joined = exchange(
+        &mut alice,
+        r#"{"type": "join", "room": "r", "player": "alice"}"#,
+    );
+    assert!(joined.starts_with(r#"{"type":"joined","room":"r","length":5"#));
+    exchange(
+        &mut bob,
+        r#"{"type": "join", "room": "r", "player": "bob"}"#,
+    );
+    assert_eq!(
+        exchange(&mut alice, ""),
+        "{\"type\":\"player_joined\",\"player\":\"bob\"}\n"
+    );
+
+    let feedback = exchange(&mut alice, r#"{"type": "guess", "word": "crane"}"#);
+    assert!(feedback.contains(r#""status":"won""#));
+    let finished = exchange(&mut bob, "");
+    assert!(finished.starts_with(r#"{"type":"player_finished","player":"alice","status":"won""#));
+    exchange(&mut bob, r#"{"type": "quit"}"#);
+    assert!(exchange(&mut bob, "").contains("round_over"));
+    assert_eq!(
+        exchange(&mut bob, ""),
+        "{\"type\":\"goodbye\",\"word\":\"CRANE\"}\n"
+    );
+
+    server.kill().unwrap();
+    server.wait().unwrap();
+}
+
+#[test]
+fn test_join() {
+    let mut file = NamedTempFile::new().unwrap();
+    writeln!(file, "crane,1\nslate,0.5").unwrap();
+    let (mut server, port) = serve(&file, &["--seed", "0", "--min-frequency", "1"]);
+    let address = format!("127.0.0.1:{port}");
+
+    // The server only chooses frequent enough words, CRANE where the seed would choose SLATE,
+    // and scores the guesses typed in the client
+    let joined = run(&["join", &address, "--name", "alice"], "slate\ncrane\n");
+    assert_eq!(joined.code, Some(0));
+    assert!(joined.stdout.contains("Joined room lobby."));
+    assert!(joined.stdout.contains("the word was CRANE, you won!"));
+    assert!(joined
+        .stdout
+        .contains("Every player has finished, the word was CRANE."));
+
+    server.kill().unwrap();
+    server.wait().unwrap();
+
+    // An unreachable server is reported as such
+    let unreachable = run(&["join", &address], "");
+    assert_eq!(unreachable.code, Some(5));
+    assert!(unreachable
+        .stderr
+        .contains(&format!("Cannot connect to {address}:")));
+}